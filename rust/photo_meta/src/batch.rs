@@ -0,0 +1,208 @@
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Mutex;
+
+use lru::LruCache;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+use rusqlite::Connection;
+
+use crate::db::get_db;
+use crate::errors::DbError;
+use crate::exif;
+use crate::geocode;
+use crate::models::{Coord, DirectionRef, ExifData, Place};
+use crate::pydatetime;
+
+/// Rounds a coordinate to ~1km precision so nearby shots share a
+/// reverse-geocode cache entry instead of each re-querying the database.
+fn round_coord(coord: Coord) -> (i64, i64) {
+    ((coord.lat() * 100.0).round() as i64, (coord.lon() * 100.0).round() as i64)
+}
+
+pub(crate) fn db_error_to_pyerr(err: DbError) -> PyErr {
+    match err {
+        DbError::Open(err) => {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to open database: {}", err))
+        }
+        DbError::Incompatible(err) => {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Database incompatible: {}", err))
+        }
+        DbError::Query(err) => {
+            PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Database query failed: {}", err))
+        }
+    }
+}
+
+/// Builds the Python-facing result dict shared by `extract_metadata` and
+/// `MetadataExtractor::extract_batch`. `date_as_string` keeps the deprecated
+/// RFC 3339 string behaviour available to callers that still need it.
+pub(crate) fn build_result_dict<'py>(
+    py: Python<'py>,
+    exif_data: &ExifData,
+    place: Option<&Place>,
+    match_source: Option<geocode::MatchSource>,
+    date_as_string: bool,
+) -> PyResult<Py<PyDict>> {
+    let dict = PyDict::new(py);
+
+    match (exif_data.timestamp, date_as_string) {
+        (Some(timestamp), true) => dict.set_item("date_taken", timestamp.to_rfc3339())?,
+        (Some(timestamp), false) => {
+            dict.set_item("date_taken", pydatetime::to_pydatetime(py, &timestamp)?)?
+        }
+        (None, _) => dict.set_item("date_taken", py.None())?,
+    }
+
+    match exif_data.lat {
+        Some(lat) => dict.set_item("lat", lat)?,
+        None => dict.set_item("lat", py.None())?,
+    }
+
+    match exif_data.lon {
+        Some(lon) => dict.set_item("lon", lon)?,
+        None => dict.set_item("lon", py.None())?,
+    }
+
+    match exif_data.altitude_meters {
+        Some(altitude) => dict.set_item("altitude_meters", altitude)?,
+        None => dict.set_item("altitude_meters", py.None())?,
+    }
+
+    match exif_data.image_direction_degrees {
+        Some(direction) => dict.set_item("image_direction_degrees", direction)?,
+        None => dict.set_item("image_direction_degrees", py.None())?,
+    }
+
+    match exif_data.image_direction_ref {
+        Some(DirectionRef::True) => dict.set_item("image_direction_ref", "true")?,
+        Some(DirectionRef::Magnetic) => dict.set_item("image_direction_ref", "magnetic")?,
+        None => dict.set_item("image_direction_ref", py.None())?,
+    }
+
+    let location_string = match place {
+        Some(place) => match &place.admin {
+            Some(admin) => format!("{}, {}, {}", place.name, admin, place.country),
+            None => format!("{}, {}", place.name, place.country),
+        },
+        None => "Unknown location".to_string(),
+    };
+    dict.set_item("location", location_string)?;
+
+    match match_source {
+        Some(geocode::MatchSource::Exact) => dict.set_item("location_match", "exact")?,
+        Some(geocode::MatchSource::Approximate) => dict.set_item("location_match", "approximate")?,
+        None => dict.set_item("location_match", py.None())?,
+    }
+
+    Ok(dict.into())
+}
+
+/// Batch metadata extractor that keeps a single validated SQLite connection
+/// and a reverse-geocode cache alive across many files, instead of paying
+/// the open-database and geocode-lookup cost once per call the way
+/// `extract_metadata` does. EXIF parsing for the batch runs in parallel
+/// across a rayon thread pool while the GIL is released; geocoding lookups
+/// share the one connection and run one at a time under its lock.
+#[pyclass]
+pub struct MetadataExtractor {
+    conn: Mutex<Connection>,
+    cache: Mutex<LruCache<(i64, i64), Option<Place>>>,
+}
+
+#[pymethods]
+impl MetadataExtractor {
+    #[new]
+    fn new(db_path: &str) -> PyResult<Self> {
+        let conn = get_db(Path::new(db_path)).map_err(db_error_to_pyerr)?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            cache: Mutex::new(LruCache::new(NonZeroUsize::new(256).unwrap())),
+        })
+    }
+
+    fn extract_batch(&self, py: Python<'_>, paths: Vec<String>) -> PyResult<Vec<Py<PyDict>>> {
+        type Row = (ExifData, Option<Place>, Option<geocode::MatchSource>);
+        let rows: Vec<Row> = py.allow_threads(|| {
+            paths
+                .par_iter()
+                .map(|path| {
+                    let exif_data = exif::extract_exif(path);
+                    let hint = geocode::NoCoordHint { utc_offset_seconds: exif_data.utc_offset_seconds };
+                    let (place, match_source) = self.resolve_place(exif_data.lat, exif_data.lon, &hint);
+                    (exif_data, place, match_source)
+                })
+                .collect()
+        });
+
+        rows.iter()
+            .map(|(exif_data, place, match_source)| {
+                build_result_dict(py, exif_data, place.as_ref(), *match_source, false)
+            })
+            .collect()
+    }
+}
+
+/// Wraps the batch's cached SQLite lookup as a [`geocode::Geocoder`] so it
+/// can sit in front of [`geocode::TimezoneGeocoder`] in a [`geocode::ChainGeocoder`],
+/// the same fallback chain `extract_metadata` uses.
+struct CachedSqliteGeocoder<'a> {
+    conn: &'a Connection,
+    cache: &'a Mutex<LruCache<(i64, i64), Option<Place>>>,
+}
+
+impl geocode::Geocoder for CachedSqliteGeocoder<'_> {
+    fn resolve(&self, coord: Coord) -> Option<Place> {
+        let key = round_coord(coord);
+        if let Some(cached) = self.cache.lock().unwrap().get(&key) {
+            return cached.clone();
+        }
+
+        let place = geocode::reverse_geocode(self.conn, coord);
+        self.cache.lock().unwrap().put(key, place.clone());
+        place
+    }
+
+    fn resolve_without_coord(&self, _hint: &geocode::NoCoordHint) -> Option<Place> {
+        None
+    }
+}
+
+impl MetadataExtractor {
+    fn resolve_place(
+        &self,
+        lat: Option<f64>,
+        lon: Option<f64>,
+        hint: &geocode::NoCoordHint,
+    ) -> (Option<Place>, Option<geocode::MatchSource>) {
+        let coord = lat.zip(lon).and_then(|(lat, lon)| Coord::new(lat, lon));
+        let conn = self.conn.lock().unwrap();
+        let primary = CachedSqliteGeocoder { conn: &conn, cache: &self.cache };
+        let chain = geocode::ChainGeocoder::new(primary, geocode::TimezoneGeocoder);
+
+        match chain.resolve_with_source(coord, hint) {
+            Some((place, source)) => (Some(place), Some(source)),
+            None => (None, None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_coord_rounds_to_two_decimal_places() {
+        let coord = Coord::new(51.50741, -0.12782).unwrap();
+        assert_eq!(round_coord(coord), (5151, -13));
+    }
+
+    #[test]
+    fn test_round_coord_groups_nearby_points() {
+        let a = Coord::new(51.5074, -0.1278).unwrap();
+        let b = Coord::new(51.5076, -0.1279).unwrap();
+        assert_eq!(round_coord(a), round_coord(b));
+    }
+}
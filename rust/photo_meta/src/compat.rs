@@ -31,6 +31,17 @@ pub fn assert_compatible(conn: &Connection) -> Result<(), PhotoMetaError> {
     Ok(())
 }
 
+/// Whether this DB file was built with the `places_rtree` spatial index.
+/// Older DB files predate the index and must fall back to a linear scan.
+pub fn has_rtree_index(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'places_rtree'",
+        [],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,4 +113,22 @@ mod tests {
         let result = assert_compatible(&conn);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_has_rtree_index_missing() {
+        let conn = Connection::open_in_memory()
+            .expect("Failed to create in-memory DB");
+        assert!(!has_rtree_index(&conn));
+    }
+
+    #[test]
+    fn test_has_rtree_index_present() {
+        let conn = Connection::open_in_memory()
+            .expect("Failed to create in-memory DB");
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE places_rtree USING rtree(id, minLat, maxLat, minLon, maxLon)"
+        ).expect("Failed to create rtree table");
+
+        assert!(has_rtree_index(&conn));
+    }
 }
\ No newline at end of file
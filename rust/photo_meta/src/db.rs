@@ -3,7 +3,7 @@ use rusqlite::{Connection, params};
 
 use crate::compat;
 use crate::errors::DbError;
-use crate::models::{Candidate, PlaceKind};
+use crate::models::{Candidate, Coord, PlaceKind};
 
 pub fn db_filename() -> &'static str {
     "places_v0.1.db"
@@ -14,7 +14,7 @@ pub fn validate_db(path: &Path) -> Result<(), DbError> {
         .map_err(DbError::Open)?;
     
     compat::assert_compatible(&conn)
-        .map_err(DbError::Incompatible)?;
+        .map_err(|err| DbError::Incompatible(err.to_string()))?;
 
     Ok(())
 }
@@ -24,48 +24,171 @@ pub fn get_db(path: &Path) -> Result<Connection, DbError> {
         .map_err(DbError::Open)?;
 
     compat::assert_compatible(&conn)
-        .map_err(DbError::Incompatible)?;
+        .map_err(|err| DbError::Incompatible(err.to_string()))?;
 
     Ok(conn)
 }
 
+/// Degrees of latitude per kilometre, used to size the bounding box from a
+/// kilometre radius.
+const DEG_PER_KM: f64 = 1.0 / 111.0;
+
 pub fn fetch_candidates(
-    path: &Path,
-    lat: f64,
-    lon: f64,
+    conn: &Connection,
+    coord: Coord,
+    radius_km: f64,
+) -> Result<Vec<Candidate>, DbError> {
+    if compat::has_rtree_index(conn) {
+        fetch_candidates_rtree(conn, coord, radius_km)
+    } else {
+        fetch_candidates_scan(conn, coord, radius_km)
+    }
+}
+
+fn row_to_candidate(row: &rusqlite::Row) -> rusqlite::Result<Candidate> {
+    let kind: String = row.get(5)?;
+    Ok(Candidate {
+        name: row.get(0)?,
+        country: row.get(1)?,
+        admin: row.get(2)?,
+        lat: row.get(3)?,
+        lon: row.get(4)?,
+        kind: match kind.as_str() {
+            "landmark" => PlaceKind::Landmark,
+            "city" => PlaceKind::City,
+            _ => PlaceKind::Town,
+        },
+        importance: row.get(6)?,
+    })
+}
+
+/// Longitude span of the bounding box, split in two when it crosses the
+/// antimeridian.
+enum LonSpan {
+    Normal { lo: f64, hi: f64 },
+    /// The window runs off the east edge (`hi > 180`) and wraps back in
+    /// from `-180`.
+    CrossesEast { lo: f64, wrapped_hi: f64 },
+    /// The window runs off the west edge (`lo < -180`) and wraps back in
+    /// from `180`.
+    CrossesWest { hi: f64, wrapped_lo: f64 },
+}
+
+struct BoundingBox {
+    lat_lo: f64,
+    lat_hi: f64,
+    lon_span: LonSpan,
+}
+
+fn wrap_lon(lon: f64) -> f64 {
+    let mut wrapped = lon;
+    while wrapped > 180.0 {
+        wrapped -= 360.0;
+    }
+    while wrapped < -180.0 {
+        wrapped += 360.0;
+    }
+    wrapped
+}
+
+/// Builds a lat/lon box approximating a constant-kilometre radius around
+/// `coord`. Longitude delta is scaled by `1/cos(lat)` since a degree of
+/// longitude shrinks towards the poles, and the window is clamped/wrapped
+/// so it stays geographically meaningful near the poles and the
+/// antimeridian.
+fn bounding_box(coord: Coord, radius_km: f64) -> BoundingBox {
+    let lat = coord.lat();
+    let lon = coord.lon();
+
+    let lat_delta = (radius_km * DEG_PER_KM).min(90.0);
+    let cos_lat = lat.to_radians().cos().max(0.01); // clamp near the poles
+    let lon_delta = (radius_km * DEG_PER_KM / cos_lat).min(180.0);
+
+    let lat_lo = (lat - lat_delta).max(-90.0);
+    let lat_hi = (lat + lat_delta).min(90.0);
+    let lon_lo = lon - lon_delta;
+    let lon_hi = lon + lon_delta;
+
+    let lon_span = if lon_hi > 180.0 {
+        LonSpan::CrossesEast { lo: lon_lo, wrapped_hi: wrap_lon(lon_hi) }
+    } else if lon_lo < -180.0 {
+        LonSpan::CrossesWest { hi: lon_hi, wrapped_lo: wrap_lon(lon_lo) }
+    } else {
+        LonSpan::Normal { lo: lon_lo, hi: lon_hi }
+    };
+
+    BoundingBox { lat_lo, lat_hi, lon_span }
+}
+
+/// Linear `places` scan, used when `places_rtree` is absent (e.g. DB files
+/// built before the spatial index was introduced).
+fn fetch_candidates_scan(
+    conn: &Connection,
+    coord: Coord,
+    radius_km: f64,
 ) -> Result<Vec<Candidate>, DbError> {
-    let conn = get_db(path)?;
+    let bbox = bounding_box(coord, radius_km);
+
+    let (lon_clause, lon_lo, lon_hi) = match bbox.lon_span {
+        LonSpan::Normal { lo, hi } => ("lon BETWEEN ? AND ?", lo, hi),
+        LonSpan::CrossesEast { lo, wrapped_hi } => ("(lon >= ? OR lon <= ?)", lo, wrapped_hi),
+        LonSpan::CrossesWest { hi, wrapped_lo } => ("(lon <= ? OR lon >= ?)", hi, wrapped_lo),
+    };
 
-    let delta = 0.5; // degrees (~55 km)
-    let mut stmt = conn.prepare(
+    let mut stmt = conn.prepare(&format!(
         r#"
         SELECT name, country, admin, lat, lon, kind, importance
         FROM places
         WHERE lat BETWEEN ? AND ?
-            AND lon BETWEEN ? AND ?
+            AND {lon_clause}
         ORDER BY importance DESC
         LIMIT 50
-        "#,
+        "#
+    )).map_err(DbError::Query)?;
+
+    let rows = stmt.query_map(
+        params![bbox.lat_lo, bbox.lat_hi, lon_lo, lon_hi],
+        row_to_candidate,
     ).map_err(DbError::Query)?;
 
+    rows.collect::<Result<_, _>>()
+        .map_err(DbError::Query)
+}
+
+/// Queries the `places_rtree` bounding-box index first (log-time range
+/// overlap), then joins back to `places` for the attribute columns.
+fn fetch_candidates_rtree(
+    conn: &Connection,
+    coord: Coord,
+    radius_km: f64,
+) -> Result<Vec<Candidate>, DbError> {
+    let bbox = bounding_box(coord, radius_km);
+
+    let (lon_clause, lon_param_a, lon_param_b) = match bbox.lon_span {
+        LonSpan::Normal { lo, hi } => ("r.minLon <= ? AND r.maxLon >= ?", hi, lo),
+        LonSpan::CrossesEast { lo, wrapped_hi } => {
+            ("(r.minLon >= ? OR r.maxLon <= ?)", lo, wrapped_hi)
+        }
+        LonSpan::CrossesWest { hi, wrapped_lo } => {
+            ("(r.maxLon <= ? OR r.minLon >= ?)", hi, wrapped_lo)
+        }
+    };
+
+    let mut stmt = conn.prepare(&format!(
+        r#"
+        SELECT p.name, p.country, p.admin, p.lat, p.lon, p.kind, p.importance
+        FROM places_rtree AS r
+        JOIN places AS p ON p.rowid = r.id
+        WHERE r.minLat <= ? AND r.maxLat >= ?
+            AND {lon_clause}
+        ORDER BY p.importance DESC
+        LIMIT 50
+        "#
+    )).map_err(DbError::Query)?;
+
     let rows = stmt.query_map(
-        params![lat - delta, lat + delta, lon - delta, lon + delta],
-        |row| {
-            let kind: String = row.get(5)?;
-            Ok(Candidate {
-                name: row.get(0)?,
-                country: row.get(1)?,
-                admin: row.get(2)?,
-                lat: row.get(3)?,
-                lon: row.get(4)?,
-                kind: match kind.as_str() {
-                    "landmark" => PlaceKind::Landmark,
-                    "city" => PlaceKind::City,
-                    _ => PlaceKind::Town,
-                },
-                importance: row.get(6)?,
-            })
-        },
+        params![bbox.lat_hi, bbox.lat_lo, lon_param_a, lon_param_b],
+        row_to_candidate,
     ).map_err(DbError::Query)?;
 
     rows.collect::<Result<_, _>>()
@@ -78,7 +201,7 @@ mod tests {
     use std::path::PathBuf;
     use tempfile::NamedTempFile;
 
-    fn setup_test_db() -> (NamedTempFile, PathBuf) {
+    fn setup_test_db() -> (NamedTempFile, Connection) {
         let temp_file = NamedTempFile::new()
             .expect("Failed to create temp file");
         let path = temp_file.path().to_path_buf();
@@ -125,14 +248,24 @@ mod tests {
             params!["Tower Bridge", "UK", Some("London"), 51.5055, -0.0754, "landmark", 0.95],
         ).expect("Failed to insert test data");
 
-        drop(conn);
-        (temp_file, path)
+        (temp_file, conn)
+    }
+
+    fn add_rtree_index(conn: &Connection) {
+        conn.execute_batch(
+            "CREATE VIRTUAL TABLE places_rtree USING rtree(id, minLat, maxLat, minLon, maxLon)"
+        ).expect("Failed to create rtree table");
+
+        conn.execute_batch(
+            "INSERT INTO places_rtree (id, minLat, maxLat, minLon, maxLon)
+             SELECT rowid, lat, lat, lon, lon FROM places"
+        ).expect("Failed to populate rtree table");
     }
 
     #[test]
     fn test_fetch_candidates_returns_all_in_range() {
-        let (_temp, path) = setup_test_db();
-        let result = fetch_candidates(&path, 51.5074, -0.1278);
+        let (_temp, conn) = setup_test_db();
+        let result = fetch_candidates(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0);
 
         assert!(result.is_ok());
         let candidates = result.unwrap();
@@ -141,8 +274,8 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_filters_by_distance() {
-        let (_temp, path) = setup_test_db();
-        let result = fetch_candidates(&path, 0.0, 0.0);
+        let (_temp, conn) = setup_test_db();
+        let result = fetch_candidates(&conn, Coord::new(0.0, 0.0).unwrap(), 55.0);
 
         assert!(result.is_ok());
         let candidates = result.unwrap();
@@ -151,12 +284,12 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_converts_string_kind_to_enum() {
-        let (_temp, path) = setup_test_db();
-        let result = fetch_candidates(&path, 51.5074, -0.1278);
+        let (_temp, conn) = setup_test_db();
+        let result = fetch_candidates(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0);
 
         assert!(result.is_ok());
         let candidates = result.unwrap();
-        
+
         for candidate in candidates {
             match candidate.name.as_str() {
                 "London" => assert_eq!(candidate.kind, PlaceKind::City),
@@ -169,9 +302,7 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_respects_limit() {
-        let (_temp, path) = setup_test_db();
-        let conn = Connection::open(&path)
-            .expect("Failed to open test DB");
+        let (_temp, conn) = setup_test_db();
 
         // Insert additional entries to exceed the limit
         for i in 0..60 {
@@ -181,9 +312,8 @@ mod tests {
                 params![name, "UK", None::<String>, 51.5 + (i as f64 * 0.001), -0.1 - (i as f64 * 0.001), "town", 0.5],
             ).expect("Failed to insert test data");
         }
-        drop(conn);
 
-        let result = fetch_candidates(&path, 51.5074, -0.1278);
+        let result = fetch_candidates(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0);
 
         assert!(result.is_ok());
         let candidates = result.unwrap();
@@ -192,12 +322,12 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_orders_by_importance() {
-        let (_temp, path) = setup_test_db();
-        let result = fetch_candidates(&path, 51.5074, -0.1278);
+        let (_temp, conn) = setup_test_db();
+        let result = fetch_candidates(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0);
 
         assert!(result.is_ok());
         let candidates = result.unwrap();
-        
+
         let mut last_importance = std::f64::INFINITY;
         for candidate in candidates {
             assert!(candidate.importance <= last_importance);
@@ -206,16 +336,43 @@ mod tests {
     }
 
     #[test]
-    fn test_fetch_candidates_with_invalid_db_path() {
-        let invalid_path = Path::new("/nonexistent/path/places.db");
-        let result = fetch_candidates(invalid_path, 51.5074, -0.1278);
+    fn test_fetch_candidates_uses_rtree_when_present() {
+        let (_temp, conn) = setup_test_db();
+        add_rtree_index(&conn);
+
+        let result = fetch_candidates(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0);
+
+        assert!(result.is_ok());
+        let candidates = result.unwrap();
+        assert_eq!(candidates.len(), 3);
+    }
+
+    #[test]
+    fn test_fetch_candidates_rtree_matches_scan_results() {
+        let (_temp, conn) = setup_test_db();
+
+        let scan_result = fetch_candidates_scan(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0).unwrap();
+
+        add_rtree_index(&conn);
+        let rtree_result = fetch_candidates_rtree(&conn, Coord::new(51.5074, -0.1278).unwrap(), 55.0).unwrap();
+
+        let mut scan_names: Vec<_> = scan_result.iter().map(|c| c.name.clone()).collect();
+        let mut rtree_names: Vec<_> = rtree_result.iter().map(|c| c.name.clone()).collect();
+        scan_names.sort();
+        rtree_names.sort();
+        assert_eq!(scan_names, rtree_names);
+    }
+
+    #[test]
+    fn test_get_db_with_invalid_path() {
+        let result = get_db(Path::new("/nonexistent/path/places.db"));
         assert!(result.is_err());
     }
 
     #[test]
     fn test_fetch_candidates_with_no_matching_entries() {
-        let (_temp, path) = setup_test_db();
-        let result = fetch_candidates(&path, 90.0, 180.0);
+        let (_temp, conn) = setup_test_db();
+        let result = fetch_candidates(&conn, Coord::new(90.0, 180.0).unwrap(), 55.0);
         assert!(result.is_ok());
         let candidates = result.unwrap();
         assert_eq!(candidates.len(), 0);
@@ -223,17 +380,14 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_with_null_admin_field() {
-        let (_temp, path) = setup_test_db();
-        let conn = Connection::open(&path)
-            .expect("Failed to open test DB");
+        let (_temp, conn) = setup_test_db();
 
         conn.execute(
             "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
             params!["NoAdminPlace", "UK", None::<String>, 51.5, -0.1, "town", 0.6],
         ).expect("Failed to insert test data");
-        drop(conn);
 
-        let result = fetch_candidates(&path, 51.5, -0.1);
+        let result = fetch_candidates(&conn, Coord::new(51.5, -0.1).unwrap(), 55.0);
         assert!(result.is_ok());
         let candidates = result.unwrap();
         let no_admin_place = candidates.iter().find(|c| c.name == "NoAdminPlace");
@@ -243,21 +397,76 @@ mod tests {
 
     #[test]
     fn test_fetch_candidates_with_unknown_kind() {
-        let (_temp, path) = setup_test_db();
-        let conn = Connection::open(&path)
-            .expect("Failed to open test DB");
+        let (_temp, conn) = setup_test_db();
 
         conn.execute(
             "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
             params!["UnknownPlace", "UK", Some("SomeAdmin"), 51.5, -0.1, "unknown_kind", 0.6],
         ).expect("Failed to insert test data");
-        drop(conn);
 
-        let result = fetch_candidates(&path, 51.5, -0.1);
+        let result = fetch_candidates(&conn, Coord::new(51.5, -0.1).unwrap(), 55.0);
         assert!(result.is_ok());
         let candidates = result.unwrap();
         let unknown = candidates.iter().find(|c| c.name == "UnknownPlace");
         assert!(unknown.is_some());
         assert_eq!(unknown.unwrap().kind, PlaceKind::Town); // Default to Town
     }
+
+    #[test]
+    fn test_fetch_candidates_crosses_antimeridian() {
+        let (_temp, conn) = setup_test_db();
+
+        conn.execute(
+            "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params!["Suva", "FJ", None::<String>, 0.0, 179.95, "town", 0.6],
+        ).expect("Failed to insert test data");
+        conn.execute(
+            "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params!["Nadi", "FJ", None::<String>, 0.0, -179.95, "town", 0.6],
+        ).expect("Failed to insert test data");
+
+        let result = fetch_candidates(&conn, Coord::new(0.0, 179.98).unwrap(), 20.0);
+        assert!(result.is_ok());
+        let names: Vec<_> = result.unwrap().iter().map(|c| c.name.clone()).collect();
+        assert!(names.contains(&"Suva".to_string()));
+        assert!(names.contains(&"Nadi".to_string()));
+    }
+
+    #[test]
+    fn test_fetch_candidates_crosses_antimeridian_rtree() {
+        let (_temp, conn) = setup_test_db();
+
+        conn.execute(
+            "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params!["Suva", "FJ", None::<String>, 0.0, 179.95, "town", 0.6],
+        ).expect("Failed to insert test data");
+        conn.execute(
+            "INSERT INTO places VALUES (?, ?, ?, ?, ?, ?, ?)",
+            params!["Nadi", "FJ", None::<String>, 0.0, -179.95, "town", 0.6],
+        ).expect("Failed to insert test data");
+        add_rtree_index(&conn);
+
+        let result = fetch_candidates(&conn, Coord::new(0.0, 179.98).unwrap(), 20.0);
+        assert!(result.is_ok());
+        let names: Vec<_> = result.unwrap().iter().map(|c| c.name.clone()).collect();
+        assert!(names.contains(&"Suva".to_string()));
+        assert!(names.contains(&"Nadi".to_string()));
+    }
+
+    #[test]
+    fn test_bounding_box_widens_longitude_near_poles() {
+        let equator = bounding_box(Coord::new(0.0, 0.0).unwrap(), 100.0);
+        let near_pole = bounding_box(Coord::new(85.0, 0.0).unwrap(), 100.0);
+
+        let equator_span = match equator.lon_span {
+            LonSpan::Normal { lo, hi } => hi - lo,
+            _ => panic!("expected a normal span"),
+        };
+        let pole_span = match near_pole.lon_span {
+            LonSpan::Normal { lo, hi } => hi - lo,
+            _ => panic!("expected a normal span"),
+        };
+
+        assert!(pole_span > equator_span);
+    }
 }
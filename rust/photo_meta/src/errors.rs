@@ -1,6 +1,35 @@
+use std::fmt;
+
 #[derive(Debug)]
 pub enum DbError {
     Open(rusqlite::Error),
     Incompatible(String),
     Query(rusqlite::Error),
-}
\ No newline at end of file
+}
+
+/// Errors surfaced outside the places-database path: DB version checks done
+/// from `compat`, and EXIF extraction that genuinely failed rather than just
+/// finding no metadata.
+#[derive(Debug)]
+pub enum PhotoMetaError {
+    Database(rusqlite::Error),
+    Incompatible { db_version: String, crate_version: String },
+    /// A container was recognized but turned out corrupt or unsupported, as
+    /// opposed to a recognized container that simply carries no EXIF.
+    Exif(String),
+}
+
+impl fmt::Display for PhotoMetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhotoMetaError::Database(err) => write!(f, "database error: {err}"),
+            PhotoMetaError::Incompatible { db_version, crate_version } => write!(
+                f,
+                "database version {db_version} is incompatible with crate version {crate_version}"
+            ),
+            PhotoMetaError::Exif(message) => write!(f, "EXIF error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for PhotoMetaError {}
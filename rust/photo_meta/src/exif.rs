@@ -1,42 +1,535 @@
 use std::fs::File;
 use std::io::BufReader;
+use std::path::Path;
 use exif::{Reader, Tag, In};
-use chrono::{DateTime, NaiveDateTime};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Offset, TimeZone, Timelike};
 
-use crate::gps::extract_gps;
+use crate::errors::PhotoMetaError;
+use crate::gps::{extract_altitude, extract_gps, extract_image_direction};
 use crate::models::ExifData;
+use crate::xmp;
+
+/// Containers understood by `extract_exif`. RAW formats from real cameras
+/// (CR2, NEF, ARW, DNG, RW2, ORF) are TIFF-backed, so they share the same
+/// parsing path as JPEG/TIFF; HEIC/HEIF/AVIF (all ISO-BMFF, sharing the same
+/// `meta`/`iinf`/`iloc` item layout) and PNG need their own handling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    TiffBacked,
+    Heif,
+    Png,
+}
+
+fn detect_container(path: &Path) -> Container {
+    match path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .as_deref()
+    {
+        Some("heic") | Some("heif") | Some("avif") => Container::Heif,
+        Some("png") => Container::Png,
+        // jpg/jpeg/tif/tiff plus the TIFF-backed RAW formats (cr2, nef, arw,
+        // dng, rw2, orf) all parse through the same TIFF/EXIF reader.
+        _ => Container::TiffBacked,
+    }
+}
+
+/// EXIF's colon-separated date form plus the ISO-8601 variants real cameras
+/// and tools emit: `T` or space as the date/time separator, optional
+/// fractional seconds, optional trailing UTC offset.
+const NAIVE_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y:%m:%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y:%m:%dT%H:%M:%S%.f",
+];
+
+const OFFSET_DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S%.f%:z",
+    "%Y:%m:%d %H:%M:%S%.f%:z",
+    "%Y-%m-%dT%H:%M:%S%.f%:z",
+    "%Y:%m:%dT%H:%M:%S%.f%:z",
+];
 
 pub fn parse_datetime(datetime_str: &str) -> Option<DateTime<chrono::Utc>> {
-    NaiveDateTime::parse_from_str(datetime_str, "%Y-%m-%d %H:%M:%S")
-        .ok()
-        .or_else(|| {
-            // Fall back to colon format for raw EXIF values
-            NaiveDateTime::parse_from_str(datetime_str, "%Y:%m:%d %H:%M:%S").ok()
-        })
+    if let Some(dt) = parse_offset_datetime(datetime_str) {
+        return Some(dt.with_timezone(&chrono::Utc));
+    }
+    parse_naive_datetime(datetime_str)
         .map(|dt| DateTime::<chrono::Utc>::from_naive_utc_and_offset(dt, chrono::Utc))
 }
 
-pub fn extract_exif(path: &str) -> ExifData {
-    let file = match File::open(path) {
-        Ok(f) => f,
-        Err(_) => return ExifData { timestamp: None, lat: None, lon: None },
-    };
+fn parse_offset_datetime(datetime_str: &str) -> Option<DateTime<FixedOffset>> {
+    OFFSET_DATETIME_FORMATS
+        .iter()
+        .find_map(|fmt| DateTime::parse_from_str(datetime_str, fmt).ok())
+}
+
+fn parse_naive_datetime(datetime_str: &str) -> Option<NaiveDateTime> {
+    NAIVE_DATETIME_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(datetime_str, fmt).ok())
+}
+
+/// Folds an EXIF `SubSecTime*` tag's digit string into nanosecond precision,
+/// e.g. `"5"` -> 500_000_000ns, `"003"` -> 3_000_000ns. Returns `None` for
+/// empty or non-numeric input.
+fn subsec_digits_to_nanos(digits: &str) -> Option<u32> {
+    let digits = digits.trim();
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let mut padded = digits.to_string();
+    padded.truncate(9);
+    while padded.len() < 9 {
+        padded.push('0');
+    }
+    padded.parse().ok()
+}
+
+/// Applies the `SubSecTimeOriginal`/`SubSecTimeDigitized`/`SubSecTime` tag to
+/// `naive`, but only when the datetime string itself carried no fractional
+/// seconds already.
+fn apply_subsec_time(exif: &exif::Exif, naive: NaiveDateTime) -> NaiveDateTime {
+    if naive.nanosecond() != 0 {
+        return naive;
+    }
+
+    let nanos = exif
+        .get_field(Tag::SubSecTimeOriginal, In::PRIMARY)
+        .or_else(|| exif.get_field(Tag::SubSecTimeDigitized, In::PRIMARY))
+        .or_else(|| exif.get_field(Tag::SubSecTime, In::PRIMARY))
+        .and_then(|f| subsec_digits_to_nanos(&f.display_value().to_string()));
+
+    match nanos {
+        Some(nanos) => naive.with_nanosecond(nanos).unwrap_or(naive),
+        None => naive,
+    }
+}
 
-    let mut bufreader = BufReader::new(file);
-    let exif = match Reader::new().read_from_container(&mut bufreader) {
-        Ok(e) => e,
-        Err(_) => return ExifData { timestamp: None, lat: None, lon: None },
+/// Parses an EXIF `OffsetTime*` value (e.g. `+02:00`) into a `FixedOffset`.
+fn parse_exif_offset(raw: &str) -> Option<FixedOffset> {
+    let raw = raw.trim();
+    let sign = match raw.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
     };
 
-    let timestamp = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)
-        .or_else(|| exif.get_field(Tag::DateTime, In::PRIMARY))
-        .and_then(|f| parse_datetime(&f.display_value().to_string()));
+    let mut parts = raw[1..].splitn(2, ':');
+    let hours: i32 = parts.next()?.parse().ok()?;
+    let minutes: i32 = parts.next().unwrap_or("0").parse().ok()?;
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Resolves the IANA timezone offset in effect at `naive` for the given GPS
+/// coordinates, falling back to `None` when no timezone boundary matches.
+fn resolve_gps_offset(naive: NaiveDateTime, lat: f64, lon: f64) -> Option<FixedOffset> {
+    let tz_name = tzf_rs::DefaultFinder::new().get_tz_name(lon, lat);
+    let tz: chrono_tz::Tz = tz_name.parse().ok()?;
+    let local = tz.from_local_datetime(&naive).single()?;
+    Some(local.offset().fix())
+}
+
+/// Builds the EXIF `OffsetTime*`/GPS-derived offset for `naive`, returning
+/// both the resolved offset (for recovering local time) and the true UTC
+/// instant. Falls back to treating `naive` as already being UTC when neither
+/// an offset tag nor GPS coordinates are available.
+fn resolve_timestamp(
+    exif: &exif::Exif,
+    naive: NaiveDateTime,
+    lat: Option<f64>,
+    lon: Option<f64>,
+) -> (DateTime<chrono::Utc>, Option<i32>) {
+    let offset = exif.get_field(Tag::OffsetTimeOriginal, In::PRIMARY)
+        .or_else(|| exif.get_field(Tag::OffsetTime, In::PRIMARY))
+        .or_else(|| exif.get_field(Tag::OffsetTimeDigitized, In::PRIMARY))
+        .and_then(|f| parse_exif_offset(&f.display_value().to_string()))
+        .or_else(|| {
+            let (lat, lon) = (lat?, lon?);
+            resolve_gps_offset(naive, lat, lon)
+        });
+
+    match offset {
+        Some(offset) => (
+            offset.from_local_datetime(&naive).single()
+                .unwrap_or_else(|| DateTime::<chrono::Utc>::from_naive_utc_and_offset(naive, chrono::Utc).fixed_offset())
+                .with_timezone(&chrono::Utc),
+            Some(offset.fix().local_minus_utc()),
+        ),
+        None => (
+            DateTime::<chrono::Utc>::from_naive_utc_and_offset(naive, chrono::Utc),
+            None,
+        ),
+    }
+}
+
+pub fn extract_exif(path: &str) -> ExifData {
+    extract_exif_checked(path).unwrap_or_else(|_| merge_xmp_sidecar(empty_exif_data(), path))
+}
+
+/// As `extract_exif`, but distinguishes a recognized container that simply
+/// carries no EXIF (`Ok` with empty fields) from one that's corrupt or
+/// unsupported (`Err(PhotoMetaError::Exif)`), instead of treating both the
+/// same way.
+pub fn extract_exif_checked(path: &str) -> Result<ExifData, PhotoMetaError> {
+    let exif = match read_exif(path, detect_container(Path::new(path)))? {
+        Some(exif) => exif,
+        None => return Ok(merge_xmp_sidecar(empty_exif_data(), path)),
+    };
 
     let (lat, lon) = extract_gps(&exif)
-        .map(|(lat, lon)| (Some(lat), Some(lon)))
+        .map(|coord| (Some(coord.lat()), Some(coord.lon())))
+        .unwrap_or((None, None));
+    let altitude_meters = extract_altitude(&exif);
+    let (image_direction_degrees, image_direction_ref) = extract_image_direction(&exif)
+        .map(|(direction, direction_ref)| (Some(direction), Some(direction_ref)))
         .unwrap_or((None, None));
 
-    ExifData { timestamp, lat, lon }
+    let naive = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)
+        .or_else(|| exif.get_field(Tag::DateTime, In::PRIMARY))
+        .and_then(|f| parse_naive_datetime(&f.display_value().to_string()))
+        .map(|naive| apply_subsec_time(&exif, naive));
+
+    let (timestamp, utc_offset_seconds) = match naive {
+        Some(naive) => {
+            let (ts, offset) = resolve_timestamp(&exif, naive, lat, lon);
+            (Some(ts), offset)
+        }
+        None => (None, None),
+    };
+
+    let data = ExifData {
+        timestamp,
+        utc_offset_seconds,
+        lat,
+        lon,
+        altitude_meters,
+        image_direction_degrees,
+        image_direction_ref,
+    };
+    Ok(if data.timestamp.is_none() || data.lat.is_none() {
+        merge_xmp_sidecar(data, path)
+    } else {
+        data
+    })
+}
+
+fn empty_exif_data() -> ExifData {
+    ExifData {
+        timestamp: None,
+        utc_offset_seconds: None,
+        lat: None,
+        lon: None,
+        altitude_meters: None,
+        image_direction_degrees: None,
+        image_direction_ref: None,
+    }
+}
+
+/// Reads embedded EXIF out of `path`, dispatching on container kind.
+/// `Ok(None)` means the container was read fine but genuinely carries no
+/// EXIF (e.g. a PNG with no `eXIf` chunk, or a JPEG with none of the usual
+/// EXIF markers). `Err` means the container itself couldn't be read —
+/// missing file, corrupt/unsupported TIFF-backed data, or an embedded EXIF
+/// payload the reader rejects — which callers should not silently treat as
+/// "no metadata". A HEIF box layout or PNG chunk layout that can't be
+/// followed is not reported as `Err`; it yields `Ok(None)`.
+fn read_exif(path: &str, container: Container) -> Result<Option<exif::Exif>, PhotoMetaError> {
+    match container {
+        Container::Png => {
+            let bytes = std::fs::read(path)
+                .map_err(|err| PhotoMetaError::Exif(format!("failed to read {path}: {err}")))?;
+            let Some(exif_bytes) = extract_png_exif_chunk(&bytes) else {
+                return Ok(None);
+            };
+            Reader::new()
+                .read_raw(exif_bytes)
+                .map(Some)
+                .map_err(|err| PhotoMetaError::Exif(format!("corrupt EXIF chunk in {path}: {err}")))
+        }
+        Container::TiffBacked => {
+            let file = File::open(path)
+                .map_err(|err| PhotoMetaError::Exif(format!("failed to open {path}: {err}")))?;
+            let mut bufreader = BufReader::new(file);
+            match Reader::new().read_from_container(&mut bufreader) {
+                Ok(exif) => Ok(Some(exif)),
+                // No EXIF IFD at all is the common, unremarkable case for
+                // plain JPEGs/TIFFs — not a corrupt file.
+                Err(exif::Error::NotFound(_)) => Ok(None),
+                Err(err) => Err(PhotoMetaError::Exif(format!(
+                    "corrupt or unsupported container {path}: {err}"
+                ))),
+            }
+        }
+        Container::Heif => {
+            let bytes = std::fs::read(path)
+                .map_err(|err| PhotoMetaError::Exif(format!("failed to read {path}: {err}")))?;
+            let Some(exif_bytes) = extract_heif_exif_payload(&bytes) else {
+                return Ok(None);
+            };
+            Reader::new()
+                .read_raw(exif_bytes)
+                .map(Some)
+                .map_err(|err| PhotoMetaError::Exif(format!("corrupt EXIF payload in {path}: {err}")))
+        }
+    }
+}
+
+/// Walks an ISO-BMFF (HEIF/HEIC/AVIF) box tree to find the raw TIFF/EXIF
+/// payload of the `Exif` item declared in the `meta` box's `iinf`/`iloc`
+/// tables, per ISO/IEC 23008-12. Returns `None` when the container has no
+/// `Exif` item (or its box layout can't be followed) — callers treat that
+/// the same as "no EXIF", matching the other container paths.
+fn extract_heif_exif_payload(bytes: &[u8]) -> Option<Vec<u8>> {
+    let meta = find_box(bytes, b"meta")?;
+    // `meta` is itself a FullBox: a 4-byte version/flags header precedes its
+    // nested boxes.
+    let meta_body = meta.get(4..)?;
+
+    let iinf = find_box(meta_body, b"iinf")?;
+    let item_id = find_exif_item_id(iinf)?;
+
+    let iloc = find_box(meta_body, b"iloc")?;
+    let (offset, length) = find_item_location(iloc, item_id)?;
+
+    let item_data = bytes.get(offset..offset.checked_add(length)?)?;
+    // Per the `Exif` item encoding, a big-endian offset to the TIFF header
+    // precedes the TIFF blob itself (usually 0, occasionally padded with a
+    // literal `Exif\0\0`).
+    let header_offset = u32::from_be_bytes(item_data.get(0..4)?.try_into().ok()?) as usize;
+    item_data.get(4usize.checked_add(header_offset)?..).map(<[u8]>::to_vec)
+}
+
+/// Finds the first immediate child box of type `want` within `bytes`,
+/// returning its body (the bytes after the size+type header). Handles the
+/// 64-bit large-size extension but not `size == 0` ("extends to end of
+/// file"), which `meta`'s nested tables never use.
+fn find_box<'a>(bytes: &'a [u8], want: &[u8]) -> Option<&'a [u8]> {
+    let mut pos = 0;
+    while pos + 8 <= bytes.len() {
+        let size32 = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+        let box_type = &bytes[pos + 4..pos + 8];
+
+        let (header_len, total_len) = if size32 == 1 {
+            let size64 = u64::from_be_bytes(bytes.get(pos + 8..pos + 16)?.try_into().ok()?) as usize;
+            (16, size64)
+        } else {
+            (8, size32)
+        };
+
+        let body_start = pos + header_len;
+        let box_end = pos.checked_add(total_len)?;
+        if total_len < header_len || box_end > bytes.len() {
+            return None;
+        }
+
+        if box_type == want {
+            return Some(&bytes[body_start..box_end]);
+        }
+        pos = box_end;
+    }
+    None
+}
+
+/// Scans an `iinf` (ItemInfoBox) body for an `infe` (ItemInfoEntry) entry
+/// whose `item_type` is `Exif`, returning its `item_ID`. Only `infe`
+/// versions 2 and 3 are understood — the versions every HEIC/AVIF encoder
+/// in practice emits.
+fn find_exif_item_id(iinf: &[u8]) -> Option<u32> {
+    let version = *iinf.first()?;
+    let count_len = if version == 0 { 2 } else { 4 };
+    let mut pos = 4 + count_len;
+
+    while pos + 8 <= iinf.len() {
+        let size = u32::from_be_bytes(iinf[pos..pos + 4].try_into().ok()?) as usize;
+        let box_type = &iinf[pos + 4..pos + 8];
+        if size < 8 {
+            return None;
+        }
+        let box_end = pos.checked_add(size)?;
+        if box_end > iinf.len() {
+            return None;
+        }
+
+        if box_type == b"infe" {
+            if let Some(item_id) = parse_infe_exif_item_id(&iinf[pos + 8..box_end]) {
+                return Some(item_id);
+            }
+        }
+        pos = box_end;
+    }
+    None
+}
+
+fn parse_infe_exif_item_id(body: &[u8]) -> Option<u32> {
+    let version = *body.first()?;
+    match version {
+        2 => {
+            let item_id = u16::from_be_bytes(body.get(4..6)?.try_into().ok()?) as u32;
+            let item_type = body.get(8..12)?;
+            (item_type == b"Exif").then_some(item_id)
+        }
+        3 => {
+            let item_id = u32::from_be_bytes(body.get(4..8)?.try_into().ok()?);
+            let item_type = body.get(10..14)?;
+            (item_type == b"Exif").then_some(item_id)
+        }
+        _ => None,
+    }
+}
+
+/// Reads an `iloc` (ItemLocationBox) body and returns the `(file_offset,
+/// length)` of `item_id`'s first extent. Only construction_method 0
+/// (file-offset based) is understood, which is what HEIC/AVIF encoders use
+/// for a same-file `Exif` item; items using any other construction method
+/// are skipped.
+fn find_item_location(iloc: &[u8], item_id: u32) -> Option<(usize, usize)> {
+    let version = *iloc.first()?;
+    let offset_size = (*iloc.get(4)? >> 4) as usize;
+    let length_size = (*iloc.get(4)? & 0x0F) as usize;
+    let base_offset_size = (*iloc.get(5)? >> 4) as usize;
+
+    let mut pos = 6;
+    let item_count = if version < 2 {
+        let count = u16::from_be_bytes(iloc.get(pos..pos + 2)?.try_into().ok()?) as usize;
+        pos += 2;
+        count
+    } else {
+        let count = u32::from_be_bytes(iloc.get(pos..pos + 4)?.try_into().ok()?) as usize;
+        pos += 4;
+        count
+    };
+
+    for _ in 0..item_count {
+        let id = if version < 2 {
+            let id = u16::from_be_bytes(iloc.get(pos..pos + 2)?.try_into().ok()?) as u32;
+            pos += 2;
+            id
+        } else {
+            let id = u32::from_be_bytes(iloc.get(pos..pos + 4)?.try_into().ok()?);
+            pos += 4;
+            id
+        };
+
+        let mut construction_method = 0;
+        if version == 1 || version == 2 {
+            // construction_method (12 reserved bits + 4-bit method)
+            construction_method = u16::from_be_bytes(iloc.get(pos..pos + 2)?.try_into().ok()?) & 0x0F;
+            pos += 2;
+        }
+        pos += 2; // data_reference_index
+
+        let base_offset = read_be_uint(iloc, pos, base_offset_size)?;
+        pos += base_offset_size;
+
+        let extent_count = u16::from_be_bytes(iloc.get(pos..pos + 2)?.try_into().ok()?) as usize;
+        pos += 2;
+
+        let mut first_extent = None;
+        for extent_index in 0..extent_count {
+            let extent_offset = read_be_uint(iloc, pos, offset_size)?;
+            pos += offset_size;
+            let extent_length = read_be_uint(iloc, pos, length_size)?;
+            pos += length_size;
+            if extent_index == 0 {
+                first_extent = Some((extent_offset, extent_length));
+            }
+        }
+
+        if id == item_id && construction_method == 0 {
+            let (extent_offset, extent_length) = first_extent?;
+            return Some(((base_offset + extent_offset) as usize, extent_length as usize));
+        }
+    }
+    None
+}
+
+fn read_be_uint(bytes: &[u8], pos: usize, size: usize) -> Option<u64> {
+    if size == 0 {
+        return Some(0);
+    }
+    let slice = bytes.get(pos..pos + size)?;
+    Some(slice.iter().fold(0u64, |acc, &b| (acc << 8) | b as u64))
+}
+
+/// Scans a PNG byte stream for an `eXIf` chunk and returns its raw TIFF
+/// payload, as produced by cameras/tools that store EXIF in PNG.
+fn extract_png_exif_chunk(bytes: &[u8]) -> Option<Vec<u8>> {
+    const SIGNATURE_LEN: usize = 8;
+    let mut pos = SIGNATURE_LEN;
+    while pos + 8 <= bytes.len() {
+        let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into().ok()?) as usize;
+        let chunk_type = &bytes[pos + 4..pos + 8];
+        let data_start = pos + 8;
+        let data_end = data_start.checked_add(len)?;
+        if data_end > bytes.len() {
+            return None;
+        }
+        if chunk_type == b"eXIf" {
+            return Some(bytes[data_start..data_end].to_vec());
+        }
+        if chunk_type == b"IEND" {
+            return None;
+        }
+        pos = data_end + 4; // skip the trailing CRC
+    }
+    None
+}
+
+/// Fills in any timestamp/GPS still missing from `data` using an adjacent
+/// `.xmp` sidecar, for files (RAW, edited exports) that keep metadata there
+/// instead of in the container's own EXIF.
+fn merge_xmp_sidecar(mut data: ExifData, path: &str) -> ExifData {
+    let Some(sidecar) = xmp::read_sidecar(Path::new(path)) else {
+        return data;
+    };
+
+    if data.timestamp.is_none() {
+        if let Some(raw) = sidecar.timestamp.as_deref() {
+            // XMP timestamps are ISO 8601 (`T` separator); normalize to the
+            // EXIF-style form the shared parser understands. An explicit
+            // offset is kept so the timestamp isn't later treated as UTC.
+            let raw = raw.replacen('T', " ", 1);
+            match parse_offset_datetime(&raw) {
+                Some(dt) => {
+                    data.timestamp = Some(dt.with_timezone(&chrono::Utc));
+                    data.utc_offset_seconds = Some(dt.offset().local_minus_utc());
+                }
+                None => data.timestamp = parse_datetime(&raw),
+            }
+        }
+    }
+
+    let had_no_coords = data.lat.is_none() && data.lon.is_none();
+    if had_no_coords {
+        data.lat = sidecar.lat;
+        data.lon = sidecar.lon;
+    }
+
+    // The timestamp may have been resolved as if it were already UTC
+    // (`utc_offset_seconds: None`) because no EXIF GPS was available to
+    // derive a zone from. Now that the sidecar may have supplied
+    // coordinates, retry that resolution so the instant isn't silently off
+    // by the camera's local offset.
+    if had_no_coords && data.utc_offset_seconds.is_none() {
+        if let (Some(timestamp), Some(lat), Some(lon)) = (data.timestamp, data.lat, data.lon) {
+            let naive = timestamp.naive_utc();
+            if let Some(offset) = resolve_gps_offset(naive, lat, lon) {
+                data.timestamp = offset
+                    .from_local_datetime(&naive)
+                    .single()
+                    .unwrap_or(timestamp.fixed_offset())
+                    .with_timezone(&chrono::Utc);
+                data.utc_offset_seconds = Some(offset.fix().local_minus_utc());
+            }
+        }
+    }
+
+    data
 }
 
 #[cfg(test)]
@@ -44,6 +537,101 @@ mod tests {
     use super::*;
     use chrono::{Datelike, Timelike};
 
+    #[test]
+    fn test_parse_exif_offset_positive() {
+        let offset = parse_exif_offset("+02:00").unwrap();
+        assert_eq!(offset.local_minus_utc(), 2 * 3600);
+    }
+
+    #[test]
+    fn test_parse_exif_offset_negative() {
+        let offset = parse_exif_offset("-05:30").unwrap();
+        assert_eq!(offset.local_minus_utc(), -(5 * 3600 + 30 * 60));
+    }
+
+    #[test]
+    fn test_parse_exif_offset_invalid() {
+        assert!(parse_exif_offset("UTC").is_none());
+        assert!(parse_exif_offset("").is_none());
+    }
+
+    #[test]
+    fn test_parse_datetime_with_fractional_seconds() {
+        let result = parse_datetime("2024:06:15 14:30:45.500");
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().timestamp_subsec_millis(), 500);
+    }
+
+    #[test]
+    fn test_parse_datetime_iso8601_t_separator() {
+        let result = parse_datetime("2024-06-15T14:30:45");
+        assert!(result.is_some());
+        let dt = result.unwrap();
+        assert_eq!(dt.hour(), 14);
+        assert_eq!(dt.minute(), 30);
+    }
+
+    #[test]
+    fn test_parse_datetime_with_offset() {
+        let result = parse_datetime("2024-06-15T14:30:45+02:00");
+        assert!(result.is_some());
+        // 14:30 local at +02:00 is 12:30 UTC
+        assert_eq!(result.unwrap().hour(), 12);
+    }
+
+    #[test]
+    fn test_parse_datetime_negative_offset() {
+        let result = parse_datetime("2024-06-15 14:30:45-05:00");
+        assert!(result.is_some());
+        assert_eq!(result.unwrap().hour(), 19);
+    }
+
+    #[test]
+    fn test_subsec_digits_to_nanos_single_digit() {
+        assert_eq!(subsec_digits_to_nanos("5"), Some(500_000_000));
+    }
+
+    #[test]
+    fn test_subsec_digits_to_nanos_full_precision() {
+        assert_eq!(subsec_digits_to_nanos("123456789"), Some(123_456_789));
+    }
+
+    #[test]
+    fn test_subsec_digits_to_nanos_rejects_non_numeric() {
+        assert_eq!(subsec_digits_to_nanos("abc"), None);
+        assert_eq!(subsec_digits_to_nanos(""), None);
+    }
+
+    #[test]
+    fn test_detect_container_by_extension() {
+        assert_eq!(detect_container(Path::new("photo.jpg")), Container::TiffBacked);
+        assert_eq!(detect_container(Path::new("photo.CR2")), Container::TiffBacked);
+        assert_eq!(detect_container(Path::new("photo.nef")), Container::TiffBacked);
+        assert_eq!(detect_container(Path::new("photo.heic")), Container::Heif);
+        assert_eq!(detect_container(Path::new("photo.HEIF")), Container::Heif);
+        assert_eq!(detect_container(Path::new("photo.avif")), Container::Heif);
+        assert_eq!(detect_container(Path::new("photo.png")), Container::Png);
+    }
+
+    #[test]
+    fn test_extract_png_exif_chunk_missing() {
+        let png = b"\x89PNG\r\n\x1a\n\x00\x00\x00\x00IHDR\x00\x00\x00\x00";
+        assert!(extract_png_exif_chunk(png).is_none());
+    }
+
+    #[test]
+    fn test_extract_png_exif_chunk_found() {
+        let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+        let exif_payload = b"fake-tiff-bytes";
+        png.extend_from_slice(&(exif_payload.len() as u32).to_be_bytes());
+        png.extend_from_slice(b"eXIf");
+        png.extend_from_slice(exif_payload);
+        png.extend_from_slice(&[0u8; 4]); // CRC placeholder
+
+        let found = extract_png_exif_chunk(&png).unwrap();
+        assert_eq!(found, exif_payload);
+    }
+
     #[test]
     fn test_parse_datetime_valid_format() {
         let result = parse_datetime("2024:06:15 14:30:45");
@@ -135,6 +723,100 @@ mod tests {
         assert!(result.lon.is_none());
     }
 
+    #[test]
+    fn test_extract_exif_checked_image_without_exif_is_ok() {
+        let fixture_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/no_exif.jpg");
+        let result = extract_exif_checked(fixture_path).unwrap();
+        assert!(result.timestamp.is_none());
+        assert!(result.lat.is_none());
+    }
+
+    #[test]
+    fn test_extract_exif_checked_corrupt_container_is_err() {
+        let fixture_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/not_an_image.txt");
+        let err = extract_exif_checked(fixture_path).unwrap_err();
+        assert!(matches!(err, PhotoMetaError::Exif(_)));
+    }
+
+    #[test]
+    fn test_extract_exif_checked_missing_file_is_err() {
+        let err = extract_exif_checked("/nonexistent/path/file.jpg").unwrap_err();
+        assert!(matches!(err, PhotoMetaError::Exif(_)));
+    }
+
+    fn make_box(box_type: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(&((8 + body.len()) as u32).to_be_bytes());
+        out.extend_from_slice(box_type);
+        out.extend_from_slice(body);
+        out
+    }
+
+    /// Assembles a synthetic `ftyp`/`meta`(`iinf`+`iloc`) ISO-BMFF prefix
+    /// declaring a single `Exif` item (id 1) whose extent lives at
+    /// `mdat_offset` within the eventual file, plus the item's own payload
+    /// (the 4-byte TIFF-header-offset field followed by `tiff_bytes`).
+    fn build_heif_prefix(mdat_offset: u32, tiff_bytes: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let mut exif_item_data = 0u32.to_be_bytes().to_vec();
+        exif_item_data.extend_from_slice(tiff_bytes);
+
+        let mut infe_body = vec![2, 0, 0, 0]; // version 2, flags 0
+        infe_body.extend_from_slice(&1u16.to_be_bytes()); // item_ID
+        infe_body.extend_from_slice(&0u16.to_be_bytes()); // item_protection_index
+        infe_body.extend_from_slice(b"Exif"); // item_type
+        let infe = make_box(b"infe", &infe_body);
+
+        let mut iinf_body = vec![0, 0, 0, 0]; // version 0, flags 0
+        iinf_body.extend_from_slice(&1u16.to_be_bytes()); // entry_count
+        iinf_body.extend_from_slice(&infe);
+        let iinf = make_box(b"iinf", &iinf_body);
+
+        let mut iloc_body = vec![0, 0, 0, 0]; // version 0, flags 0
+        iloc_body.push(0x44); // offset_size=4, length_size=4
+        iloc_body.push(0x00); // base_offset_size=0, index_size=0
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // item_count
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // item_ID
+        iloc_body.extend_from_slice(&0u16.to_be_bytes()); // data_reference_index
+        iloc_body.extend_from_slice(&1u16.to_be_bytes()); // extent_count
+        iloc_body.extend_from_slice(&mdat_offset.to_be_bytes()); // extent_offset
+        iloc_body.extend_from_slice(&(exif_item_data.len() as u32).to_be_bytes()); // extent_length
+        let iloc = make_box(b"iloc", &iloc_body);
+
+        let mut meta_body = vec![0, 0, 0, 0]; // FullBox version/flags
+        meta_body.extend_from_slice(&iinf);
+        meta_body.extend_from_slice(&iloc);
+        let meta = make_box(b"meta", &meta_body);
+
+        let ftyp = make_box(b"ftyp", b"heic\0\0\0\0heic");
+
+        let mut prefix = ftyp;
+        prefix.extend_from_slice(&meta);
+        (prefix, exif_item_data)
+    }
+
+    #[test]
+    fn test_extract_heif_exif_payload_from_synthetic_iso_bmff() {
+        let tiff_bytes = b"fake-tiff-bytes";
+
+        // First pass just to measure how long the ftyp+meta prefix is, so
+        // the `mdat` extent offset we declare in `iloc` is accurate.
+        let (probe_prefix, _) = build_heif_prefix(0, tiff_bytes);
+        let mdat_offset = (probe_prefix.len() + 8) as u32; // + mdat's own box header
+
+        let (prefix, exif_item_data) = build_heif_prefix(mdat_offset, tiff_bytes);
+        let mut file = prefix;
+        file.extend_from_slice(&make_box(b"mdat", &exif_item_data));
+
+        let payload = extract_heif_exif_payload(&file).unwrap();
+        assert_eq!(payload, tiff_bytes);
+    }
+
+    #[test]
+    fn test_extract_heif_exif_payload_missing_exif_item_is_none() {
+        let ftyp = make_box(b"ftyp", b"heic\0\0\0\0heic");
+        assert!(extract_heif_exif_payload(&ftyp).is_none());
+    }
+
     #[test]
     fn test_extract_exif_with_complete_data() {
         let fixture_path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/complete_exif.jpg");
@@ -163,4 +845,64 @@ mod tests {
         assert!(result.lat.is_none());
         assert!(result.lon.is_none());
     }
+
+    #[test]
+    fn test_merge_xmp_sidecar_retries_offset_once_coords_arrive() {
+        let dir = tempfile::tempdir().unwrap();
+        let image_path = dir.path().join("shot.jpg");
+        std::fs::write(&image_path, b"").unwrap();
+        std::fs::write(
+            dir.path().join("shot.xmp"),
+            r#"<rdf:Description exif:GPSLatitude="40.7128" exif:GPSLongitude="-74.0060"/>"#,
+        )
+        .unwrap();
+
+        let naive = parse_naive_datetime("2024:06:15 14:30:45").unwrap();
+        let data = ExifData {
+            timestamp: Some(DateTime::<chrono::Utc>::from_naive_utc_and_offset(naive, chrono::Utc)),
+            utc_offset_seconds: None,
+            lat: None,
+            lon: None,
+            altitude_meters: None,
+            image_direction_degrees: None,
+            image_direction_ref: None,
+        };
+
+        let merged = merge_xmp_sidecar(data, image_path.to_str().unwrap());
+
+        assert_eq!(merged.lat, Some(40.7128));
+        assert_eq!(merged.lon, Some(-74.0060));
+        assert!(merged.utc_offset_seconds.is_some());
+        assert_ne!(merged.timestamp.unwrap().hour(), 14);
+    }
+
+    #[test]
+    fn test_merge_xmp_sidecar_leaves_utc_alone_without_sidecar_coords() {
+        let dir = tempfile::tempdir().unwrap();
+        let image_path = dir.path().join("shot.jpg");
+        std::fs::write(&image_path, b"").unwrap();
+        std::fs::write(
+            dir.path().join("shot.xmp"),
+            r#"<rdf:Description exif:DateTimeOriginal="2024-06-15T14:30:45"/>"#,
+        )
+        .unwrap();
+
+        let naive = parse_naive_datetime("2024:06:15 14:30:45").unwrap();
+        let data = ExifData {
+            timestamp: Some(DateTime::<chrono::Utc>::from_naive_utc_and_offset(naive, chrono::Utc)),
+            utc_offset_seconds: None,
+            lat: None,
+            lon: None,
+            altitude_meters: None,
+            image_direction_degrees: None,
+            image_direction_ref: None,
+        };
+
+        let merged = merge_xmp_sidecar(data, image_path.to_str().unwrap());
+
+        assert!(merged.lat.is_none());
+        assert!(merged.lon.is_none());
+        assert!(merged.utc_offset_seconds.is_none());
+        assert_eq!(merged.timestamp.unwrap().hour(), 14);
+    }
 }
\ No newline at end of file
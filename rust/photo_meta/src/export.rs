@@ -0,0 +1,292 @@
+use std::path::Path;
+
+use pyo3::prelude::*;
+use serde_json::json;
+
+use crate::batch::db_error_to_pyerr;
+use crate::db::get_db;
+use crate::exif;
+use crate::geocode;
+use crate::models::{Coord, DirectionRef, ExifData, Place};
+
+/// One photo's extracted EXIF plus its resolved place, as produced by
+/// `extract_exif` and `reverse_geocode` — the pair the `export` functions
+/// turn into standard geospatial formats.
+pub struct ExtractedMetadata {
+    pub path: String,
+    pub exif: ExifData,
+    pub place: Option<Place>,
+}
+
+fn location_string(place: Option<&Place>) -> String {
+    match place {
+        Some(place) => match &place.admin {
+            Some(admin) => format!("{}, {}, {}", place.name, admin, place.country),
+            None => format!("{}, {}", place.name, place.country),
+        },
+        None => "Unknown location".to_string(),
+    }
+}
+
+/// Builds a GeoJSON `FeatureCollection` with one `Point` feature per photo
+/// that carried GPS. Photos without GPS are skipped, since GeoJSON has no
+/// standard way to place a feature with no geometry on a map.
+pub fn to_geojson(features: &[ExtractedMetadata]) -> String {
+    let geojson_features: Vec<_> = features
+        .iter()
+        .filter_map(|feature| {
+            let lat = feature.exif.lat?;
+            let lon = feature.exif.lon?;
+
+            let mut coordinates = vec![lon, lat];
+            if let Some(altitude) = feature.exif.altitude_meters {
+                coordinates.push(altitude);
+            }
+
+            Some(json!({
+                "type": "Feature",
+                "geometry": {
+                    "type": "Point",
+                    "coordinates": coordinates,
+                },
+                "properties": {
+                    "date_taken": feature.exif.timestamp.map(|ts| ts.to_rfc3339()),
+                    "location": location_string(feature.place.as_ref()),
+                    "path": feature.path,
+                    "altitude_meters": feature.exif.altitude_meters,
+                    "image_direction_degrees": feature.exif.image_direction_degrees,
+                },
+            }))
+        })
+        .collect();
+
+    json!({
+        "type": "FeatureCollection",
+        "features": geojson_features,
+    })
+    .to_string()
+}
+
+/// Builds a KML document with one `<Placemark>` per photo that carried GPS.
+/// Photos with a capture time get a `<TimeStamp><when>` set to its RFC3339
+/// form so time-aware viewers can animate the photos as a track.
+pub fn to_kml(features: &[ExtractedMetadata]) -> String {
+    let mut placemarks = String::new();
+
+    for feature in features {
+        let (Some(lat), Some(lon)) = (feature.exif.lat, feature.exif.lon) else {
+            continue;
+        };
+
+        placemarks.push_str("    <Placemark>\n");
+        placemarks.push_str(&format!("      <name>{}</name>\n", xml_escape(&feature.path)));
+        placemarks.push_str(&format!(
+            "      <description>{}</description>\n",
+            xml_escape(&location_string(feature.place.as_ref()))
+        ));
+        if let Some(timestamp) = feature.exif.timestamp {
+            placemarks.push_str("      <TimeStamp>\n");
+            placemarks.push_str(&format!("        <when>{}</when>\n", timestamp.to_rfc3339()));
+            placemarks.push_str("      </TimeStamp>\n");
+        }
+        if let Some(heading) = feature.exif.image_direction_degrees {
+            let reference = match feature.exif.image_direction_ref {
+                Some(DirectionRef::Magnetic) => "magnetic",
+                _ => "true",
+            };
+            placemarks.push_str("      <ExtendedData>\n");
+            placemarks.push_str(&format!(
+                "        <Data name=\"heading\"><value>{heading}</value></Data>\n"
+            ));
+            placemarks.push_str(&format!(
+                "        <Data name=\"heading_ref\"><value>{reference}</value></Data>\n"
+            ));
+            placemarks.push_str("      </ExtendedData>\n");
+        }
+        placemarks.push_str("      <Point>\n");
+        let coordinates = match feature.exif.altitude_meters {
+            Some(altitude) => {
+                placemarks.push_str("        <extrude>1</extrude>\n");
+                placemarks.push_str("        <altitudeMode>absolute</altitudeMode>\n");
+                format!("{lon},{lat},{altitude}")
+            }
+            None => format!("{lon},{lat}"),
+        };
+        placemarks.push_str(&format!("        <coordinates>{coordinates}</coordinates>\n"));
+        placemarks.push_str("      </Point>\n");
+        placemarks.push_str("    </Placemark>\n");
+    }
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <kml xmlns=\"http://www.opengis.net/kml/2.2\">\n\
+         <Document>\n\
+         {}\
+         </Document>\n\
+         </kml>\n",
+        placemarks
+    )
+}
+
+/// Extracts EXIF and resolves a place for each of `paths`, for the
+/// `export_geojson`/`export_kml` pyfunctions below.
+fn build_features(paths: &[String], db_path: &str) -> PyResult<Vec<ExtractedMetadata>> {
+    let conn = get_db(Path::new(db_path)).map_err(db_error_to_pyerr)?;
+
+    Ok(paths
+        .iter()
+        .map(|path| {
+            let exif_data = exif::extract_exif(path);
+            let place = exif_data
+                .lat
+                .zip(exif_data.lon)
+                .and_then(|(lat, lon)| Coord::new(lat, lon))
+                .and_then(|coord| geocode::reverse_geocode(&conn, coord));
+
+            ExtractedMetadata { path: path.clone(), exif: exif_data, place }
+        })
+        .collect())
+}
+
+/// Builds a GeoJSON `FeatureCollection` for `paths`, so a photo collection
+/// can be dropped straight into mapping tools without an intermediate file.
+#[pyfunction]
+pub fn export_geojson(paths: Vec<String>, db_path: &str) -> PyResult<String> {
+    let features = build_features(&paths, db_path)?;
+    Ok(to_geojson(&features))
+}
+
+/// Builds a KML document for `paths`, so a photo collection can be dropped
+/// straight into mapping tools without an intermediate file.
+#[pyfunction]
+pub fn export_kml(paths: Vec<String>, db_path: &str) -> PyResult<String> {
+    let features = build_features(&paths, db_path)?;
+    Ok(to_kml(&features))
+}
+
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::PlaceKind;
+
+    fn with_gps(path: &str) -> ExtractedMetadata {
+        ExtractedMetadata {
+            path: path.to_string(),
+            exif: ExifData {
+                timestamp: Some("2024-06-15T14:30:45Z".parse().unwrap()),
+                utc_offset_seconds: Some(0),
+                lat: Some(51.5074),
+                lon: Some(-0.1278),
+                altitude_meters: None,
+                image_direction_degrees: None,
+                image_direction_ref: None,
+            },
+            place: Some(Place {
+                name: "London".to_string(),
+                country: "UK".to_string(),
+                admin: None,
+                kind: PlaceKind::City,
+            }),
+        }
+    }
+
+    fn with_altitude_and_heading(path: &str) -> ExtractedMetadata {
+        let mut feature = with_gps(path);
+        feature.exif.altitude_meters = Some(35.5);
+        feature.exif.image_direction_degrees = Some(187.3);
+        feature.exif.image_direction_ref = Some(DirectionRef::True);
+        feature
+    }
+
+    fn without_gps(path: &str) -> ExtractedMetadata {
+        ExtractedMetadata {
+            path: path.to_string(),
+            exif: ExifData {
+                timestamp: None,
+                utc_offset_seconds: None,
+                lat: None,
+                lon: None,
+                altitude_meters: None,
+                image_direction_degrees: None,
+                image_direction_ref: None,
+            },
+            place: None,
+        }
+    }
+
+    #[test]
+    fn test_to_geojson_empty_input_is_valid_empty_collection() {
+        let geojson = to_geojson(&[]);
+        let parsed: serde_json::Value = serde_json::from_str(&geojson).unwrap();
+        assert_eq!(parsed["type"], "FeatureCollection");
+        assert_eq!(parsed["features"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_to_geojson_skips_photos_without_gps() {
+        let features = [with_gps("/a.jpg"), without_gps("/b.jpg")];
+        let parsed: serde_json::Value = serde_json::from_str(&to_geojson(&features)).unwrap();
+        assert_eq!(parsed["features"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_to_geojson_point_coordinates_are_lon_lat_order() {
+        let features = [with_gps("/a.jpg")];
+        let parsed: serde_json::Value = serde_json::from_str(&to_geojson(&features)).unwrap();
+        let coords = parsed["features"][0]["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(coords[0], -0.1278);
+        assert_eq!(coords[1], 51.5074);
+        assert_eq!(parsed["features"][0]["properties"]["location"], "London, UK");
+    }
+
+    #[test]
+    fn test_to_geojson_includes_altitude_and_heading() {
+        let features = [with_altitude_and_heading("/a.jpg")];
+        let parsed: serde_json::Value = serde_json::from_str(&to_geojson(&features)).unwrap();
+        let coords = parsed["features"][0]["geometry"]["coordinates"].as_array().unwrap();
+        assert_eq!(coords.len(), 3);
+        assert_eq!(coords[2], 35.5);
+        assert_eq!(parsed["features"][0]["properties"]["altitude_meters"], 35.5);
+        assert_eq!(parsed["features"][0]["properties"]["image_direction_degrees"], 187.3);
+    }
+
+    #[test]
+    fn test_to_kml_empty_input_has_no_placemarks() {
+        let kml = to_kml(&[]);
+        assert!(kml.contains("<kml"));
+        assert!(!kml.contains("<Placemark>"));
+    }
+
+    #[test]
+    fn test_to_kml_includes_timestamp_and_coordinates() {
+        let features = [with_gps("/a.jpg")];
+        let kml = to_kml(&features);
+        assert!(kml.contains("<TimeStamp>"));
+        assert!(kml.contains("<when>2024-06-15T14:30:45+00:00</when>"));
+        assert!(kml.contains("<coordinates>-0.1278,51.5074</coordinates>"));
+    }
+
+    #[test]
+    fn test_to_kml_includes_altitude_and_heading() {
+        let features = [with_altitude_and_heading("/a.jpg")];
+        let kml = to_kml(&features);
+        assert!(kml.contains("<coordinates>-0.1278,51.5074,35.5</coordinates>"));
+        assert!(kml.contains("<extrude>1</extrude>"));
+        assert!(kml.contains("<Data name=\"heading\"><value>187.3</value></Data>"));
+        assert!(kml.contains("<Data name=\"heading_ref\"><value>true</value></Data>"));
+    }
+
+    #[test]
+    fn test_to_kml_skips_photos_without_gps() {
+        let features = [without_gps("/b.jpg")];
+        assert!(!to_kml(&features).contains("<Placemark>"));
+    }
+}
@@ -0,0 +1,90 @@
+use pyo3::prelude::*;
+
+use crate::exif;
+
+/// Computes the WGS84 bounding box of `points` as
+/// `[min_lon, min_lat, max_lon, max_lat]`, or `None` if `points` is empty.
+///
+/// Antimeridian-aware: if the naive longitude span exceeds 180°, the points
+/// are assumed to straddle ±180° (consistent with the short-way-around
+/// distance `haversine` already computes for such pairs), and the returned
+/// box uses the crossing convention `min_lon > max_lon`.
+pub fn bounding_box(points: &[(f64, f64)]) -> Option<[f64; 4]> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let min_lat = points.iter().map(|(lat, _)| *lat).fold(f64::INFINITY, f64::min);
+    let max_lat = points.iter().map(|(lat, _)| *lat).fold(f64::NEG_INFINITY, f64::max);
+
+    let naive_min_lon = points.iter().map(|(_, lon)| *lon).fold(f64::INFINITY, f64::min);
+    let naive_max_lon = points.iter().map(|(_, lon)| *lon).fold(f64::NEG_INFINITY, f64::max);
+
+    if naive_max_lon - naive_min_lon <= 180.0 {
+        return Some([naive_min_lon, min_lat, naive_max_lon, max_lat]);
+    }
+
+    // The naive span exceeds 180°: shift negative longitudes into [180, 360)
+    // so a cluster straddling the antimeridian becomes contiguous, take its
+    // extent there, then map the result back into [-180, 180].
+    let shifted = |lon: f64| if lon < 0.0 { lon + 360.0 } else { lon };
+    let shifted_min = points.iter().map(|(_, lon)| shifted(*lon)).fold(f64::INFINITY, f64::min);
+    let shifted_max = points.iter().map(|(_, lon)| shifted(*lon)).fold(f64::NEG_INFINITY, f64::max);
+
+    let unshift = |lon: f64| if lon > 180.0 { lon - 360.0 } else { lon };
+    Some([unshift(shifted_min), min_lat, unshift(shifted_max), max_lat])
+}
+
+/// Computes the bounding box of a photo collection by extracting GPS from
+/// each path in `paths` and aggregating the ones that carried a coordinate.
+/// Returns `None` if none of the photos had GPS.
+#[pyfunction]
+pub fn collection_bounding_box(paths: Vec<String>) -> Option<[f64; 4]> {
+    let points: Vec<(f64, f64)> = paths
+        .iter()
+        .filter_map(|path| {
+            let data = exif::extract_exif(path);
+            data.lat.zip(data.lon)
+        })
+        .collect();
+
+    bounding_box(&points)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounding_box_empty_is_none() {
+        assert!(bounding_box(&[]).is_none());
+    }
+
+    #[test]
+    fn test_bounding_box_simple_cluster() {
+        let points = [(51.5, -0.1), (48.8, 2.3), (52.5, 13.4)];
+        let bbox = bounding_box(&points).unwrap();
+        assert_eq!(bbox, [-0.1, 48.8, 13.4, 52.5]);
+    }
+
+    #[test]
+    fn test_bounding_box_single_point() {
+        let points = [(40.0, -74.0)];
+        assert_eq!(bounding_box(&points), Some([-74.0, 40.0, -74.0, 40.0]));
+    }
+
+    #[test]
+    fn test_bounding_box_crosses_antimeridian() {
+        let points = [(0.0, 179.9), (0.0, -179.9)];
+        let bbox = bounding_box(&points).unwrap();
+        assert!(bbox[0] > bbox[2], "expected crossing convention min_lon > max_lon, got {:?}", bbox);
+        assert!((bbox[0] - 179.9).abs() < 1e-9);
+        assert!((bbox[2] - -179.9).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_collection_bounding_box_ignores_photos_without_gps() {
+        let bbox = collection_bounding_box(vec!["/nonexistent/a.jpg".to_string(), "/nonexistent/b.jpg".to_string()]);
+        assert!(bbox.is_none());
+    }
+}
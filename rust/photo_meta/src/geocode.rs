@@ -1,13 +1,264 @@
-use std::path::Path;
+use rusqlite::Connection;
+
 use crate::db;
-use crate::models::Place;
+use crate::models::{Coord, Place, PlaceKind};
 use crate::scoring;
 
+/// Starting search radius, matching `scoring`'s own default match cutoff.
+const INITIAL_RADIUS_KM: f64 = scoring::DEFAULT_MAX_DISTANCE_KM;
+/// Widen the search this many times (doubling each time) before settling
+/// for whatever the last ring found, so sparse regions still yield a
+/// nearest place instead of an endless search.
+const MAX_EXPANSIONS: u32 = 3;
+/// Stop expanding once at least this many candidates score within range —
+/// enough for `select_best` to have a meaningful choice.
+const MIN_SCORED_CANDIDATES: usize = 3;
+
 pub fn reverse_geocode(
-    db_path: &Path,
-    lat: f64,
-    lon: f64,
+    conn: &Connection,
+    coord: Coord,
 ) -> Option<Place> {
-    let candidates = db::fetch_candidates(db_path, lat, lon).ok()?;
-    scoring::select_best(candidates, lat, lon)
+    let mut radius_km = INITIAL_RADIUS_KM;
+    let mut candidates = db::fetch_candidates(conn, coord, radius_km).ok()?;
+
+    for _ in 0..MAX_EXPANSIONS {
+        // `radius_km` is also the live match cutoff passed to `score` below,
+        // so a wider ring can actually surface (and select) a more distant
+        // candidate instead of just re-querying the same 50 km cutoff.
+        let scored = candidates.iter().filter(|c| scoring::score(c, coord, radius_km).is_some()).count();
+        if scored >= MIN_SCORED_CANDIDATES {
+            break;
+        }
+
+        radius_km *= 2.0;
+        candidates = db::fetch_candidates(conn, coord, radius_km).ok()?;
+    }
+
+    scoring::select_best(candidates, coord, radius_km)
+}
+
+/// Coarse, GPS-free signals a [`Geocoder`] can use to guess at a photo's
+/// location when `extract_gps` found nothing — currently just the resolved
+/// UTC offset, since that's the one hint already on hand from EXIF.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoCoordHint {
+    pub utc_offset_seconds: Option<i32>,
+}
+
+/// A source of reverse-geocoding results. Separate from any one backend so
+/// callers (like [`ChainGeocoder`]) can combine an exact, GPS-based lookup
+/// with a coarse, coordinate-free fallback.
+pub trait Geocoder {
+    /// Resolves a place from a real GPS coordinate.
+    fn resolve(&self, coord: Coord) -> Option<Place>;
+
+    /// Best-effort place when no coordinate is available at all. Backends
+    /// that only work from coordinates should return `None`.
+    fn resolve_without_coord(&self, hint: &NoCoordHint) -> Option<Place>;
+}
+
+/// The existing SQLite candidate-database lookup, exposed as a `Geocoder`.
+pub struct SqliteGeocoder<'a> {
+    conn: &'a Connection,
+}
+
+impl<'a> SqliteGeocoder<'a> {
+    pub fn new(conn: &'a Connection) -> Self {
+        Self { conn }
+    }
+}
+
+impl Geocoder for SqliteGeocoder<'_> {
+    fn resolve(&self, coord: Coord) -> Option<Place> {
+        reverse_geocode(self.conn, coord)
+    }
+
+    fn resolve_without_coord(&self, _hint: &NoCoordHint) -> Option<Place> {
+        None
+    }
+}
+
+/// Maps a whole-hour UTC offset to a representative country/region. Meant
+/// as a last resort for photos with no GPS at all — accurate to "which
+/// timezone", not to any specific place within it.
+const TIMEZONE_REGIONS: &[(i32, &str, &str)] = &[
+    (0, "UK", "Greenwich Mean Time zone"),
+    (1, "FR", "Central European Time zone"),
+    (2, "FI", "Eastern European Time zone"),
+    (-5, "US", "Eastern Time zone"),
+    (-6, "US", "Central Time zone"),
+    (-7, "US", "Mountain Time zone"),
+    (-8, "US", "Pacific Time zone"),
+    (9, "JP", "Japan Standard Time zone"),
+    (10, "AU", "Australian Eastern Time zone"),
+];
+
+/// Offline fallback that resolves an approximate region straight from the
+/// EXIF-derived UTC offset, for photos that carry a timestamp but no GPS.
+pub struct TimezoneGeocoder;
+
+impl Geocoder for TimezoneGeocoder {
+    fn resolve(&self, _coord: Coord) -> Option<Place> {
+        None
+    }
+
+    fn resolve_without_coord(&self, hint: &NoCoordHint) -> Option<Place> {
+        let offset_seconds = hint.utc_offset_seconds?;
+        if offset_seconds % 3600 != 0 {
+            return None;
+        }
+        let offset_hours = offset_seconds / 3600;
+        TIMEZONE_REGIONS
+            .iter()
+            .find(|(offset, _, _)| *offset == offset_hours)
+            .map(|(_, country, admin)| Place {
+                name: admin.to_string(),
+                country: country.to_string(),
+                admin: None,
+                kind: PlaceKind::Town,
+            })
+    }
+}
+
+/// Whether a [`ChainGeocoder`] result came from an exact GPS lookup or a
+/// coarse, coordinate-free fallback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchSource {
+    Exact,
+    Approximate,
+}
+
+/// Tries `primary` when a coordinate is available, falling through to
+/// `fallback` (and flagging the result as approximate) when it isn't or
+/// comes up empty.
+pub struct ChainGeocoder<P, F> {
+    primary: P,
+    fallback: F,
+}
+
+impl<P: Geocoder, F: Geocoder> ChainGeocoder<P, F> {
+    pub fn new(primary: P, fallback: F) -> Self {
+        Self { primary, fallback }
+    }
+
+    pub fn resolve_with_source(
+        &self,
+        coord: Option<Coord>,
+        hint: &NoCoordHint,
+    ) -> Option<(Place, MatchSource)> {
+        if let Some(coord) = coord {
+            if let Some(place) = self.primary.resolve(coord) {
+                return Some((place, MatchSource::Exact));
+            }
+        }
+
+        self.fallback
+            .resolve_without_coord(hint)
+            .map(|place| (place, MatchSource::Approximate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AlwaysSome;
+    impl Geocoder for AlwaysSome {
+        fn resolve(&self, _coord: Coord) -> Option<Place> {
+            Some(Place {
+                name: "Exact Place".to_string(),
+                country: "UK".to_string(),
+                admin: None,
+                kind: PlaceKind::City,
+            })
+        }
+
+        fn resolve_without_coord(&self, _hint: &NoCoordHint) -> Option<Place> {
+            None
+        }
+    }
+
+    struct AlwaysNone;
+    impl Geocoder for AlwaysNone {
+        fn resolve(&self, _coord: Coord) -> Option<Place> {
+            None
+        }
+
+        fn resolve_without_coord(&self, _hint: &NoCoordHint) -> Option<Place> {
+            None
+        }
+    }
+
+    #[test]
+    fn test_timezone_geocoder_resolves_known_offset() {
+        let geocoder = TimezoneGeocoder;
+        let hint = NoCoordHint { utc_offset_seconds: Some(-5 * 3600) };
+
+        let place = geocoder.resolve_without_coord(&hint);
+        assert!(place.is_some());
+        assert_eq!(place.unwrap().country, "US");
+    }
+
+    #[test]
+    fn test_timezone_geocoder_unknown_offset_is_none() {
+        let geocoder = TimezoneGeocoder;
+        let hint = NoCoordHint { utc_offset_seconds: Some(13 * 3600) };
+
+        assert!(geocoder.resolve_without_coord(&hint).is_none());
+    }
+
+    #[test]
+    fn test_timezone_geocoder_missing_offset_is_none() {
+        let geocoder = TimezoneGeocoder;
+        let hint = NoCoordHint::default();
+
+        assert!(geocoder.resolve_without_coord(&hint).is_none());
+    }
+
+    #[test]
+    fn test_timezone_geocoder_never_resolves_from_coord() {
+        let geocoder = TimezoneGeocoder;
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        assert!(geocoder.resolve(coord).is_none());
+    }
+
+    #[test]
+    fn test_chain_geocoder_prefers_exact_match() {
+        let chain = ChainGeocoder::new(AlwaysSome, TimezoneGeocoder);
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+
+        let (place, source) = chain.resolve_with_source(Some(coord), &NoCoordHint::default()).unwrap();
+        assert_eq!(place.name, "Exact Place");
+        assert_eq!(source, MatchSource::Exact);
+    }
+
+    #[test]
+    fn test_chain_geocoder_falls_back_without_coord() {
+        let chain = ChainGeocoder::new(AlwaysNone, TimezoneGeocoder);
+        let hint = NoCoordHint { utc_offset_seconds: Some(9 * 3600) };
+
+        let (place, source) = chain.resolve_with_source(None, &hint).unwrap();
+        assert_eq!(place.country, "JP");
+        assert_eq!(source, MatchSource::Approximate);
+    }
+
+    #[test]
+    fn test_chain_geocoder_falls_back_when_primary_finds_nothing() {
+        let chain = ChainGeocoder::new(AlwaysNone, TimezoneGeocoder);
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        let hint = NoCoordHint { utc_offset_seconds: Some(0) };
+
+        let (place, source) = chain.resolve_with_source(Some(coord), &hint).unwrap();
+        assert_eq!(place.country, "UK");
+        assert_eq!(source, MatchSource::Approximate);
+    }
+
+    #[test]
+    fn test_chain_geocoder_none_when_everything_fails() {
+        let chain = ChainGeocoder::new(AlwaysNone, TimezoneGeocoder);
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        let hint = NoCoordHint::default();
+
+        assert!(chain.resolve_with_source(Some(coord), &hint).is_none());
+    }
 }
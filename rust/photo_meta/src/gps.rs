@@ -0,0 +1,85 @@
+use exif::{Exif, In, Tag, Value};
+
+use crate::models::{Coord, DirectionRef};
+
+/// Sane bounds for `GPSAltitude` — roughly the Dead Sea shore up to 10 km,
+/// above the highest summit — so an obviously-corrupt GPS reading doesn't
+/// leak into metadata.
+const MIN_ALTITUDE_M: f64 = -500.0;
+const MAX_ALTITUDE_M: f64 = 10_000.0;
+
+/// Extracts a validated `Coord` from the `GPSLatitude`/`GPSLongitude` EXIF
+/// tags, applying the hemisphere reference (`GPSLatitudeRef`/
+/// `GPSLongitudeRef`) and the `-90..=90`/`-180..=180` bounds check that
+/// `Coord::new` owns.
+pub fn extract_gps(exif: &Exif) -> Option<Coord> {
+    let lat = dms_to_decimal(exif, Tag::GPSLatitude, Tag::GPSLatitudeRef, b'S')?;
+    let lon = dms_to_decimal(exif, Tag::GPSLongitude, Tag::GPSLongitudeRef, b'W')?;
+    Coord::new(lat, lon)
+}
+
+/// Converts an EXIF degrees/minutes/seconds rational triple into signed
+/// decimal degrees, negating when the reference tag names the negative
+/// hemisphere (`negative_ref` is `S` for latitude, `W` for longitude).
+fn dms_to_decimal(exif: &Exif, value_tag: Tag, ref_tag: Tag, negative_ref: u8) -> Option<f64> {
+    let field = exif.get_field(value_tag, In::PRIMARY)?;
+    let dms = match &field.value {
+        Value::Rational(values) if values.len() == 3 => values,
+        _ => return None,
+    };
+
+    let degrees = dms[0].to_f64();
+    let minutes = dms[1].to_f64();
+    let seconds = dms[2].to_f64();
+    let mut decimal = degrees + minutes / 60.0 + seconds / 3600.0;
+
+    if let Some(reference) = exif.get_field(ref_tag, In::PRIMARY) {
+        let sign = reference.display_value().to_string();
+        if sign.as_bytes().first() == Some(&negative_ref) {
+            decimal = -decimal;
+        }
+    }
+
+    Some(decimal)
+}
+
+/// Reads `GPSAltitude`, negating it when `GPSAltitudeRef` marks the
+/// reading as below sea level (ref byte `1`), and validates the result
+/// against a sane altitude range.
+pub fn extract_altitude(exif: &Exif) -> Option<f64> {
+    let field = exif.get_field(Tag::GPSAltitude, In::PRIMARY)?;
+    let altitude = match &field.value {
+        Value::Rational(values) if !values.is_empty() => values[0].to_f64(),
+        _ => return None,
+    };
+
+    let below_sea_level = exif
+        .get_field(Tag::GPSAltitudeRef, In::PRIMARY)
+        .is_some_and(|f| matches!(&f.value, Value::Byte(bytes) if bytes.first() == Some(&1)));
+    let altitude = if below_sea_level { -altitude } else { altitude };
+
+    (MIN_ALTITUDE_M..=MAX_ALTITUDE_M).contains(&altitude).then_some(altitude)
+}
+
+/// Reads `GPSImgDirection` — the compass bearing the camera was pointing —
+/// along with `GPSImgDirectionRef`, which says whether that bearing is
+/// relative to true (`T`) or magnetic (`M`) north.
+pub fn extract_image_direction(exif: &Exif) -> Option<(f64, DirectionRef)> {
+    let field = exif.get_field(Tag::GPSImgDirection, In::PRIMARY)?;
+    let direction = match &field.value {
+        Value::Rational(values) if !values.is_empty() => values[0].to_f64(),
+        _ => return None,
+    };
+    if !(0.0..360.0).contains(&direction) {
+        return None;
+    }
+
+    let direction_ref = match exif.get_field(Tag::GPSImgDirectionRef, In::PRIMARY) {
+        Some(field) if field.display_value().to_string().as_bytes().first() == Some(&b'M') => {
+            DirectionRef::Magnetic
+        }
+        _ => DirectionRef::True,
+    };
+
+    Some((direction, direction_ref))
+}
@@ -0,0 +1,404 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use rusqlite::{params, Connection};
+
+use crate::compat;
+use crate::errors::DbError;
+use crate::models::PlaceKind;
+
+/// Typed column mapping for a CSV ingest, so callers can point at whatever
+/// header layout their export uses (e.g. GeoNames' `allCountries.txt`).
+#[derive(Debug, Clone)]
+pub struct CsvColumns {
+    pub name: String,
+    pub country: String,
+    pub admin: Option<String>,
+    pub lat: String,
+    pub lon: String,
+    pub population: Option<String>,
+    pub feature_code: Option<String>,
+}
+
+/// Input encoding accepted by [`build_db`]. The CSV variant carries the
+/// column mapping since, unlike JSONL, a CSV header layout isn't fixed.
+#[derive(Debug, Clone)]
+pub enum Format {
+    Csv(CsvColumns),
+    Jsonl,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Stats {
+    pub imported: usize,
+    pub skipped: usize,
+}
+
+struct Row {
+    name: String,
+    country: String,
+    admin: Option<String>,
+    lat: f64,
+    lon: f64,
+    population: Option<f64>,
+    feature_code: Option<String>,
+}
+
+/// Builds `places_v0.1.db` at `out` from a GeoNames-style export at `input`,
+/// classifying each row into a [`PlaceKind`] and deriving an importance
+/// score. Inserts happen inside a single transaction for throughput.
+pub fn build_db(input: &Path, format: &Format, out: &Path) -> Result<Stats, DbError> {
+    let mut conn = Connection::open(out).map_err(DbError::Open)?;
+
+    create_schema(&conn)?;
+
+    let rows: Vec<Option<Row>> = match format {
+        Format::Csv(columns) => read_csv(input, columns)?,
+        Format::Jsonl => read_jsonl(input)?,
+    };
+
+    let mut stats = Stats::default();
+
+    let tx = conn.transaction().map_err(DbError::Query)?;
+    for row in rows {
+        match row {
+            Some(row) => {
+                let kind = classify(row.feature_code.as_deref());
+                let importance = importance_score(kind, row.population);
+
+                tx.execute(
+                    "INSERT INTO places (name, country, admin, lat, lon, kind, importance)
+                     VALUES (?, ?, ?, ?, ?, ?, ?)",
+                    params![
+                        row.name,
+                        row.country,
+                        row.admin,
+                        row.lat,
+                        row.lon,
+                        kind_to_str(kind),
+                        importance,
+                    ],
+                )
+                .map_err(DbError::Query)?;
+
+                tx.execute(
+                    "INSERT INTO places_rtree (id, minLat, maxLat, minLon, maxLon)
+                     VALUES (?, ?, ?, ?, ?)",
+                    params![tx.last_insert_rowid(), row.lat, row.lat, row.lon, row.lon],
+                )
+                .map_err(DbError::Query)?;
+
+                stats.imported += 1;
+            }
+            None => stats.skipped += 1,
+        }
+    }
+    tx.commit().map_err(DbError::Query)?;
+
+    Ok(stats)
+}
+
+fn create_schema(conn: &Connection) -> Result<(), DbError> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS meta (
+            key TEXT PRIMARY KEY,
+            value TEXT
+        );
+        CREATE TABLE IF NOT EXISTS places (
+            name TEXT,
+            country TEXT,
+            admin TEXT,
+            lat REAL,
+            lon REAL,
+            kind TEXT,
+            importance REAL
+        );
+        CREATE VIRTUAL TABLE IF NOT EXISTS places_rtree USING rtree(id, minLat, maxLat, minLon, maxLon);",
+    )
+    .map_err(DbError::Query)?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO meta VALUES (?, ?)",
+        params!["db_version", compat::crate_version()],
+    )
+    .map_err(DbError::Query)?;
+
+    Ok(())
+}
+
+fn kind_to_str(kind: PlaceKind) -> &'static str {
+    match kind {
+        PlaceKind::Landmark => "landmark",
+        PlaceKind::City => "city",
+        PlaceKind::Town => "town",
+    }
+}
+
+/// Classifies a GeoNames feature code into a [`PlaceKind`]. Capitals and
+/// administrative seats (`PPLC`/`PPLA*`) count as cities, other populated
+/// places (`PPL*`) as towns, and everything else (castles, monuments,
+/// mountains, ruins, ...) as landmarks.
+fn classify(feature_code: Option<&str>) -> PlaceKind {
+    match feature_code {
+        Some(code) if code.starts_with("PPLC") || code.starts_with("PPLA") => PlaceKind::City,
+        Some(code) if code.starts_with("PPL") => PlaceKind::Town,
+        Some(_) => PlaceKind::Landmark,
+        None => PlaceKind::Town,
+    }
+}
+
+/// Normalized log population, boosted for landmarks since they're often
+/// unpopulated (a castle has no residents but is still worth surfacing).
+fn importance_score(kind: PlaceKind, population: Option<f64>) -> f64 {
+    let base = population
+        .filter(|p| *p > 0.0)
+        .map(|p| (p.ln() / 15.0).clamp(0.0, 1.0))
+        .unwrap_or(0.1);
+
+    match kind {
+        PlaceKind::Landmark => (base + 0.3).min(1.0),
+        PlaceKind::City | PlaceKind::Town => base,
+    }
+}
+
+fn read_csv(input: &Path, columns: &CsvColumns) -> Result<Vec<Option<Row>>, DbError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_path(input)
+        .map_err(|e| DbError::Incompatible(e.to_string()))?;
+
+    let headers = reader.headers().map_err(|e| DbError::Incompatible(e.to_string()))?.clone();
+    let index_of = |field: &str| headers.iter().position(|h| h == field);
+
+    let name_idx = index_of(&columns.name);
+    let country_idx = index_of(&columns.country);
+    let admin_idx = columns.admin.as_deref().and_then(&index_of);
+    let lat_idx = index_of(&columns.lat);
+    let lon_idx = index_of(&columns.lon);
+    let population_idx = columns.population.as_deref().and_then(&index_of);
+    let feature_code_idx = columns.feature_code.as_deref().and_then(&index_of);
+
+    let mut rows = Vec::new();
+    for result in reader.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(_) => {
+                rows.push(None);
+                continue;
+            }
+        };
+
+        let row = (|| {
+            let name = name_idx.and_then(|i| record.get(i))?.to_string();
+            let country = country_idx.and_then(|i| record.get(i))?.to_string();
+            let lat: f64 = lat_idx.and_then(|i| record.get(i))?.parse().ok()?;
+            let lon: f64 = lon_idx.and_then(|i| record.get(i))?.parse().ok()?;
+            let admin = admin_idx
+                .and_then(|i| record.get(i))
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+            let population = population_idx
+                .and_then(|i| record.get(i))
+                .and_then(|s| s.parse().ok());
+            let feature_code = feature_code_idx
+                .and_then(|i| record.get(i))
+                .filter(|s| !s.is_empty())
+                .map(str::to_string);
+
+            Some(Row {
+                name,
+                country,
+                admin,
+                lat,
+                lon,
+                population,
+                feature_code,
+            })
+        })();
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+fn read_jsonl(input: &Path) -> Result<Vec<Option<Row>>, DbError> {
+    let file = File::open(input).map_err(DbError::Open)?;
+    let reader = BufReader::new(file);
+
+    let mut rows = Vec::new();
+    for line in reader.lines() {
+        let line = line.map_err(DbError::Open)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let row = serde_json::from_str::<serde_json::Value>(&line)
+            .ok()
+            .and_then(|value| {
+                let name = value.get("name")?.as_str()?.to_string();
+                let country = value.get("country")?.as_str()?.to_string();
+                let lat = value.get("lat")?.as_f64()?;
+                let lon = value.get("lon")?.as_f64()?;
+                let admin = value
+                    .get("admin")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+                let population = value.get("population").and_then(|v| v.as_f64());
+                let feature_code = value
+                    .get("feature_code")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string);
+
+                Some(Row {
+                    name,
+                    country,
+                    admin,
+                    lat,
+                    lon,
+                    population,
+                    feature_code,
+                })
+            });
+
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn test_classify_capital_is_city() {
+        assert_eq!(classify(Some("PPLC")), PlaceKind::City);
+    }
+
+    #[test]
+    fn test_classify_admin_seat_is_city() {
+        assert_eq!(classify(Some("PPLA2")), PlaceKind::City);
+    }
+
+    #[test]
+    fn test_classify_populated_place_is_town() {
+        assert_eq!(classify(Some("PPL")), PlaceKind::Town);
+    }
+
+    #[test]
+    fn test_classify_other_code_is_landmark() {
+        assert_eq!(classify(Some("CSTL")), PlaceKind::Landmark);
+    }
+
+    #[test]
+    fn test_classify_missing_code_defaults_to_town() {
+        assert_eq!(classify(None), PlaceKind::Town);
+    }
+
+    #[test]
+    fn test_importance_score_landmark_boosted() {
+        let town = importance_score(PlaceKind::Town, Some(1000.0));
+        let landmark = importance_score(PlaceKind::Landmark, Some(1000.0));
+        assert!(landmark > town);
+    }
+
+    #[test]
+    fn test_importance_score_missing_population_is_low() {
+        let score = importance_score(PlaceKind::Town, None);
+        assert!(score < 0.5);
+    }
+
+    #[test]
+    fn test_build_db_from_jsonl() {
+        let input = NamedTempFile::new().unwrap();
+        std::fs::write(
+            input.path(),
+            concat!(
+                "{\"name\": \"London\", \"country\": \"UK\", \"admin\": \"England\", \"lat\": 51.5074, \"lon\": -0.1278, \"population\": 8900000, \"feature_code\": \"PPLC\"}\n",
+                "{\"name\": \"Broken\"}\n",
+            ),
+        )
+        .unwrap();
+
+        let out = NamedTempFile::new().unwrap();
+        let stats = build_db(input.path(), &Format::Jsonl, out.path()).unwrap();
+
+        assert_eq!(stats.imported, 1);
+        assert_eq!(stats.skipped, 1);
+
+        let conn = Connection::open(out.path()).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM places", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_build_db_from_csv() {
+        let input = NamedTempFile::new().unwrap();
+        std::fs::write(
+            input.path(),
+            "place_name,cc,admin1,latitude,longitude,pop,fcode\n\
+             London,UK,England,51.5074,-0.1278,8900000,PPLC\n\
+             Bad Row,UK,,notanumber,-0.1,1,PPL\n",
+        )
+        .unwrap();
+
+        let columns = CsvColumns {
+            name: "place_name".to_string(),
+            country: "cc".to_string(),
+            admin: Some("admin1".to_string()),
+            lat: "latitude".to_string(),
+            lon: "longitude".to_string(),
+            population: Some("pop".to_string()),
+            feature_code: Some("fcode".to_string()),
+        };
+
+        let out = NamedTempFile::new().unwrap();
+        let stats = build_db(input.path(), &Format::Csv(columns), out.path()).unwrap();
+
+        assert_eq!(stats.imported, 1);
+        assert_eq!(stats.skipped, 1);
+    }
+
+    #[test]
+    fn test_build_db_populates_rtree_index() {
+        let input = NamedTempFile::new().unwrap();
+        std::fs::write(
+            input.path(),
+            "{\"name\": \"London\", \"country\": \"UK\", \"lat\": 51.5074, \"lon\": -0.1278}\n",
+        )
+        .unwrap();
+
+        let out = NamedTempFile::new().unwrap();
+        build_db(input.path(), &Format::Jsonl, out.path()).unwrap();
+
+        let conn = Connection::open(out.path()).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM places_rtree", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn test_build_db_stamps_crate_version() {
+        let input = NamedTempFile::new().unwrap();
+        std::fs::write(input.path(), "").unwrap();
+
+        let out = NamedTempFile::new().unwrap();
+        build_db(input.path(), &Format::Jsonl, out.path()).unwrap();
+
+        let conn = Connection::open(out.path()).unwrap();
+        let version: String = conn
+            .query_row(
+                "SELECT value FROM meta WHERE key = 'db_version'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(version, compat::crate_version());
+    }
+}
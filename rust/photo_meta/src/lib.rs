@@ -1,57 +1,56 @@
 use pyo3::prelude::*;
 use crate::db::{get_db};
 
+mod batch;
 mod compat;
 mod db;
+mod errors;
 pub mod exif;
+pub mod export;
+pub mod extent;
+pub mod ingest;
 pub mod gps;
 mod geocode;
 mod haversine;
 pub mod models;
+mod pydatetime;
+mod scoring;
+mod track;
+mod xmp;
+
+use batch::MetadataExtractor;
 
 #[pyfunction]
 fn extract_metadata(path: &str, db_path: &str) -> PyResult<Py<pyo3::types::PyDict>> {
-    let exif_data = exif::extract_exif(path);
+    extract_metadata_impl(path, db_path, false)
+}
 
-    Python::attach(|py| {
-        let dict = pyo3::types::PyDict::new(py);
+/// Deprecated: identical to `extract_metadata`, but keeps serializing
+/// `date_taken` as an RFC 3339 string for callers that have not migrated to
+/// the native `datetime.datetime` value yet.
+#[pyfunction]
+fn extract_metadata_rfc3339(path: &str, db_path: &str) -> PyResult<Py<pyo3::types::PyDict>> {
+    extract_metadata_impl(path, db_path, true)
+}
 
-        if let Some(timestamp) = exif_data.timestamp {
-            dict.set_item("date_taken", timestamp.to_rfc3339())?;
-        } else {
-            dict.set_item("date_taken", py.None())?;
-        }
+fn extract_metadata_impl(path: &str, db_path: &str, date_as_string: bool) -> PyResult<Py<pyo3::types::PyDict>> {
+    let exif_data = exif::extract_exif(path);
 
-        if let Some(lat) = exif_data.lat {
-            dict.set_item("lat", lat)?;
-        } else {
-            dict.set_item("lat", py.None())?;
-        }
+    Python::attach(|py| {
+        let coord = exif_data.lat.zip(exif_data.lon)
+            .and_then(|(lat, lon)| models::Coord::new(lat, lon));
 
-        if let Some(lon) = exif_data.lon {
-            dict.set_item("lon", lon)?;
-        } else {
-            dict.set_item("lon", py.None())?;
-        }
+        let db = get_db(std::path::Path::new(db_path))
+            .map_err(|_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to open database"))?;
+        let hint = geocode::NoCoordHint { utc_offset_seconds: exif_data.utc_offset_seconds };
+        let chain = geocode::ChainGeocoder::new(geocode::SqliteGeocoder::new(&db), geocode::TimezoneGeocoder);
 
-        if let (Some(lat), Some(lon)) = (exif_data.lat, exif_data.lon) {
-            let db = get_db(std::path::Path::new(db_path))
-                .map_err(|_| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("Failed to open database"))?;
-            let location_string = geocode::reverse_geocode(&db, lat, lon)
-                .map(|place| {
-                    match place.admin {
-                        Some(admin) => format!("{}, {}, {}", place.name, admin, place.country),
-                        None => format!("{}, {}", place.name, place.country),
-                    }
-                })
-                .unwrap_or_else(|| "Unknown location".to_string());
-            
-            dict.set_item("location", location_string)?;
-        } else {
-            dict.set_item("location", "Unknown location")?;
-        }
+        let (place, match_source) = match chain.resolve_with_source(coord, &hint) {
+            Some((place, source)) => (Some(place), Some(source)),
+            None => (None, None),
+        };
 
-        Ok(dict.into())
+        batch::build_result_dict(py, &exif_data, place.as_ref(), match_source, date_as_string)
     })
 }
 
@@ -64,17 +63,25 @@ fn db_filename() -> &'static str {
 fn validate_db(path: &str) -> PyResult<()> {
     crate::db::validate_db(std::path::Path::new(path))
         .map_err(|e| match e {
-            crate::db::DbError::Open(err) => 
+            crate::errors::DbError::Open(err) =>
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to open database: {}", err)),
-            crate::db::DbError::Incompatible(err) => 
+            crate::errors::DbError::Incompatible(err) =>
                 PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Database incompatible: {}", err)),
+            crate::errors::DbError::Query(err) =>
+                PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Database query failed: {}", err)),
         })
 }
 
 #[pymodule]
 fn photo_meta(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(extract_metadata, m)?)?;
+    m.add_function(wrap_pyfunction!(extract_metadata_rfc3339, m)?)?;
     m.add_function(wrap_pyfunction!(db_filename, m)?)?;
     m.add_function(wrap_pyfunction!(validate_db, m)?)?;
+    m.add_function(wrap_pyfunction!(track::geotag_from_track, m)?)?;
+    m.add_function(wrap_pyfunction!(extent::collection_bounding_box, m)?)?;
+    m.add_function(wrap_pyfunction!(export::export_geojson, m)?)?;
+    m.add_function(wrap_pyfunction!(export::export_kml, m)?)?;
+    m.add_class::<MetadataExtractor>()?;
     Ok(())
 }
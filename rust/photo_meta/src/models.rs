@@ -1,10 +1,78 @@
 use chrono::DateTime;
 
+/// A validated latitude/longitude pair. `Coord::new` is the only way to
+/// construct one, so once a piece of code holds a `Coord` the `-90..=90`/
+/// `-180..=180` bounds check never needs repeating downstream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    lat: f64,
+    lon: f64,
+}
+
+impl Coord {
+    pub fn new(lat: f64, lon: f64) -> Option<Self> {
+        if (-90.0..=90.0).contains(&lat) && (-180.0..=180.0).contains(&lon) {
+            Some(Self { lat, lon })
+        } else {
+            None
+        }
+    }
+
+    pub fn lat(&self) -> f64 {
+        self.lat
+    }
+
+    pub fn lon(&self) -> f64 {
+        self.lon
+    }
+
+    /// Truncates to whole-degree tile indices, e.g. for coarse spatial
+    /// bucketing or cache keys.
+    pub fn trunc(&self) -> (i32, i32) {
+        (self.lat.trunc() as i32, self.lon.trunc() as i32)
+    }
+}
+
+impl From<Coord> for (f64, f64) {
+    fn from(coord: Coord) -> Self {
+        (coord.lat, coord.lon)
+    }
+}
+
+impl TryFrom<(f64, f64)> for Coord {
+    type Error = ();
+
+    fn try_from((lat, lon): (f64, f64)) -> Result<Self, Self::Error> {
+        Coord::new(lat, lon).ok_or(())
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExifData {
     pub timestamp: Option<DateTime<chrono::Utc>>,
+    /// Offset (seconds east of UTC) the timestamp was originally recorded in,
+    /// resolved from EXIF `OffsetTime*` tags or, failing that, from the GPS
+    /// coordinates. `None` when neither source was available and `timestamp`
+    /// was assumed to already be UTC.
+    pub utc_offset_seconds: Option<i32>,
     pub lat: Option<f64>,
     pub lon: Option<f64>,
+    /// Height above (or, if negative, below) sea level in metres, from
+    /// `GPSAltitude`/`GPSAltitudeRef`.
+    pub altitude_meters: Option<f64>,
+    /// Compass bearing the camera was pointing, from `GPSImgDirection`.
+    pub image_direction_degrees: Option<f64>,
+    /// Whether `image_direction_degrees` is relative to true or magnetic
+    /// north, from `GPSImgDirectionRef`.
+    pub image_direction_ref: Option<DirectionRef>,
+}
+
+/// Whether a GPS image-direction bearing is relative to true north or
+/// magnetic north, per EXIF `GPSImgDirectionRef`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectionRef {
+    True,
+    Magnetic,
 }
 
 #[derive(Debug, Clone)]
@@ -32,3 +100,52 @@ pub enum PlaceKind {
     City,
     Town,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coord_new_accepts_valid_bounds() {
+        assert!(Coord::new(90.0, 180.0).is_some());
+        assert!(Coord::new(-90.0, -180.0).is_some());
+        assert!(Coord::new(51.5074, -0.1278).is_some());
+    }
+
+    #[test]
+    fn test_coord_new_rejects_out_of_range() {
+        assert!(Coord::new(90.1, 0.0).is_none());
+        assert!(Coord::new(0.0, 180.1).is_none());
+        assert!(Coord::new(-90.1, 0.0).is_none());
+        assert!(Coord::new(0.0, -180.1).is_none());
+    }
+
+    #[test]
+    fn test_coord_accessors() {
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        assert_eq!(coord.lat(), 51.5074);
+        assert_eq!(coord.lon(), -0.1278);
+    }
+
+    #[test]
+    fn test_coord_trunc() {
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        assert_eq!(coord.trunc(), (51, 0));
+    }
+
+    #[test]
+    fn test_coord_try_from_tuple() {
+        let coord: Coord = (51.5074, -0.1278).try_into().unwrap();
+        assert_eq!(coord.lat(), 51.5074);
+
+        let result: Result<Coord, _> = (91.0, 0.0).try_into();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_coord_into_tuple() {
+        let coord = Coord::new(51.5074, -0.1278).unwrap();
+        let (lat, lon): (f64, f64) = coord.into();
+        assert_eq!((lat, lon), (51.5074, -0.1278));
+    }
+}
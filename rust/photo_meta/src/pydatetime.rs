@@ -0,0 +1,81 @@
+use chrono::{DateTime, Datelike, NaiveDate, Timelike, Utc};
+use pyo3::exceptions::PyUserWarning;
+use pyo3::prelude::*;
+use pyo3::types::{PyDateTime, PyTzInfo};
+
+/// Converts a chrono UTC timestamp into a timezone-aware Python `datetime`.
+///
+/// `datetime.datetime` has no representation for leap seconds. chrono
+/// represents one by keeping `second()` at 59 and pushing `nanosecond()`
+/// into the `1_000_000_000..2_000_000_000` range, so that's what we check
+/// for — `second()` itself never reaches 60 — mirroring how pyo3's own
+/// chrono conversions detect the same edge case. The leap nanosecond is
+/// truncated away and a `UserWarning` is raised.
+pub fn to_pydatetime<'py>(py: Python<'py>, dt: &DateTime<Utc>) -> PyResult<Bound<'py, PyDateTime>> {
+    if dt.nanosecond() >= 1_000_000_000 {
+        PyErr::warn(
+            py,
+            &py.get_type::<PyUserWarning>(),
+            &format!("truncating leap second in timestamp {dt} to 59"),
+            0,
+        )?;
+    }
+
+    let micros = dt.nanosecond() % 1_000_000_000 / 1_000;
+
+    PyDateTime::new(
+        py,
+        dt.year(),
+        dt.month() as u8,
+        dt.day() as u8,
+        dt.hour() as u8,
+        dt.minute() as u8,
+        dt.second() as u8,
+        micros,
+        Some(&PyTzInfo::utc(py)?),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_to_pydatetime_preserves_fields() {
+        Python::attach(|py| {
+            let dt = Utc.with_ymd_and_hms(2024, 6, 15, 14, 30, 45).unwrap();
+            let py_dt = to_pydatetime(py, &dt).unwrap();
+            assert_eq!(py_dt.get_year(), 2024);
+            assert_eq!(py_dt.get_month(), 6);
+            assert_eq!(py_dt.get_day(), 15);
+            assert_eq!(py_dt.get_hour(), 14);
+            assert_eq!(py_dt.get_minute(), 30);
+            assert_eq!(py_dt.get_second(), 45);
+        });
+    }
+
+    #[test]
+    fn test_to_pydatetime_truncates_leap_second() {
+        Python::attach(|py| {
+            let leap_time = chrono::NaiveTime::from_hms_nano_opt(23, 59, 59, 1_500_000_000).unwrap();
+            let dt = NaiveDate::from_ymd_opt(2016, 12, 31)
+                .unwrap()
+                .and_time(leap_time)
+                .and_utc();
+
+            let py_dt = to_pydatetime(py, &dt).unwrap();
+            assert_eq!(py_dt.get_second(), 59);
+            assert_eq!(py_dt.get_microsecond(), 500_000);
+
+            // Turn warnings into errors so we can confirm the UserWarning
+            // actually fires for this leap-second instant.
+            let warnings = py.import("warnings").unwrap();
+            warnings.call_method1("simplefilter", ("error",)).unwrap();
+            let result = to_pydatetime(py, &dt);
+            warnings.call_method1("simplefilter", ("default",)).unwrap();
+
+            assert!(result.is_err(), "expected a UserWarning for a leap second");
+        });
+    }
+}
@@ -0,0 +1,142 @@
+use crate::haversine::haversine;
+use crate::models::{Candidate, Coord, Place, PlaceKind};
+
+/// Default match radius, for callers that aren't doing their own ring
+/// expansion. [`geocode::reverse_geocode`](crate::geocode::reverse_geocode)
+/// widens this as it searches sparser regions, passing its live search
+/// radius through as `max_distance_km` so a wider ring can actually match.
+pub const DEFAULT_MAX_DISTANCE_KM: f64 = 50.0;
+
+pub fn score(c: &Candidate, coord: Coord, max_distance_km: f64) -> Option<f64> {
+    let candidate_coord = Coord::new(c.lat, c.lon)?;
+    let distance = haversine(coord, candidate_coord);
+    if distance > max_distance_km {
+        return None;
+    }
+
+    let kind_bias = match c.kind {
+        PlaceKind::Landmark => 8.0,
+        PlaceKind::City => 3.0,
+        PlaceKind::Town => 1.0,
+    };
+
+    Some(
+        -distance * 1.0
+            + c.importance * 2.5
+            + kind_bias
+    )
+}
+
+pub fn select_best(candidates: Vec<Candidate>, coord: Coord, max_distance_km: f64) -> Option<Place> {
+    candidates
+        .iter()
+        .filter_map(|c| score(c, coord, max_distance_km).map(|s| (c, s)))
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+        .map(|(candidate, _)| Place {
+            name: candidate.name.clone(),
+            country: candidate.country.clone(),
+            admin: candidate.admin.clone(),
+            kind: candidate.kind,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coord(lat: f64, lon: f64) -> Coord {
+        Coord::new(lat, lon).unwrap()
+    }
+
+    fn create_test_candidate(
+        name: &str,
+        lat: f64,
+        lon: f64,
+        kind: PlaceKind,
+        importance: f64,
+    ) -> Candidate {
+        Candidate {
+            name: name.to_string(),
+            country: "UK".to_string(),
+            admin: Some("Test Admin".to_string()),
+            lat,
+            lon,
+            kind,
+            importance,
+        }
+    }
+
+    #[test]
+    fn test_score_within_distance_threshold() {
+        let candidate = create_test_candidate("London", 51.5074, -0.1278, PlaceKind::City, 0.9);
+        let score = score(&candidate, coord(51.5074, -0.1278), DEFAULT_MAX_DISTANCE_KM);
+        assert!(score.is_some());
+        assert!(score.unwrap() > 0.0); // High score for exact location
+    }
+
+    #[test]
+    fn test_score_beyond_distance_threshold() {
+        let candidate = create_test_candidate("London", 51.5074, -0.1278, PlaceKind::City, 0.9);
+        let score = score(&candidate, coord(0.0, 0.0), DEFAULT_MAX_DISTANCE_KM);
+        assert!(score.is_none()); // Beyond 50 km
+    }
+
+    #[test]
+    fn test_score_landmark_has_highest_bias() {
+        let landmark = create_test_candidate("Tower of London", 51.5081, -0.0759, PlaceKind::Landmark, 0.5);
+        let city = create_test_candidate("London", 51.5074, -0.1278, PlaceKind::City, 0.5);
+        let town = create_test_candidate("Richmond", 51.4415, -0.3005, PlaceKind::Town, 0.5);
+
+        let test_coord = coord(51.5074, -0.1278);
+
+        let landmark_score = score(&landmark, test_coord, DEFAULT_MAX_DISTANCE_KM).unwrap();
+        let city_score = score(&city, test_coord, DEFAULT_MAX_DISTANCE_KM).unwrap();
+        let town_score = score(&town, test_coord, DEFAULT_MAX_DISTANCE_KM).unwrap();
+
+        assert!(landmark_score > city_score);
+        assert!(city_score > town_score);
+    }
+
+    #[test]
+    fn test_select_best_returns_highest_scoring_candidate() {
+        let candidates = vec![
+            create_test_candidate("London", 51.5074, -0.1278, PlaceKind::City, 0.9),
+            create_test_candidate("Richmond", 51.4415, -0.3005, PlaceKind::Town, 0.7),
+            create_test_candidate("Camden", 51.5416, -0.1425, PlaceKind::Town, 0.6),
+        ];
+
+        let result = select_best(candidates, coord(51.5074, -0.1278), DEFAULT_MAX_DISTANCE_KM);
+        assert!(result.is_some());
+        let place = result.unwrap();
+        assert_eq!(place.name, "London");
+    }
+
+    #[test]
+    fn test_select_best_returns_none_when_no_candidates() {
+        let candidates = vec![];
+        let result = select_best(candidates, coord(51.5074, -0.1278), DEFAULT_MAX_DISTANCE_KM);
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn test_score_respects_caller_supplied_distance_cutoff() {
+        let candidate = create_test_candidate("Paris", 48.8566, 2.3522, PlaceKind::City, 0.95);
+        let test_coord = coord(51.5074, -0.1278); // ~340 km from Paris
+
+        assert!(score(&candidate, test_coord, DEFAULT_MAX_DISTANCE_KM).is_none());
+        assert!(score(&candidate, test_coord, 400.0).is_some());
+    }
+
+    #[test]
+    fn test_select_best_filters_out_distant_candidates() {
+        let candidates = vec![
+            create_test_candidate("London", 51.5074, -0.1278, PlaceKind::City, 0.9),
+            create_test_candidate("Paris", 48.8566, 2.3522, PlaceKind::City, 0.95), // ~340 km away
+        ];
+
+        let result = select_best(candidates, coord(51.5074, -0.1278), DEFAULT_MAX_DISTANCE_KM);
+        assert!(result.is_some());
+        let place = result.unwrap();
+        assert_eq!(place.name, "London"); // Paris should be filtered out
+    }
+}
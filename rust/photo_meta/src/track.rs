@@ -0,0 +1,183 @@
+use chrono::{DateTime, Utc};
+use pyo3::prelude::*;
+
+use crate::exif;
+
+/// How far outside the track's time range `T` may fall and still snap to
+/// the nearest endpoint, instead of being rejected as untracked.
+const TOLERANCE_SECONDS: i64 = 60;
+
+struct TrackPoint {
+    time: DateTime<Utc>,
+    lat: f64,
+    lon: f64,
+}
+
+/// Geotags a photo with no GPS of its own by interpolating its position from
+/// a GPX track log. `photo_timestamp` is parsed with the same EXIF datetime
+/// parser used elsewhere in the crate; `gpx_path` is a GPX file containing a
+/// `<trkpt>` track. Returns `None` when `photo_timestamp` can't be parsed,
+/// the GPX carries no points, or `photo_timestamp` falls too far outside the
+/// track's range; raises `RuntimeError` when the GPX file can't be read.
+#[pyfunction]
+pub fn geotag_from_track(photo_timestamp: &str, gpx_path: &str) -> PyResult<Option<(f64, f64)>> {
+    let Some(photo_time) = exif::parse_datetime(photo_timestamp) else {
+        return Ok(None);
+    };
+
+    let contents = std::fs::read_to_string(gpx_path).map_err(|err| {
+        PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Failed to read GPX file: {}", err))
+    })?;
+
+    Ok(interpolate(&parse_gpx(&contents), photo_time))
+}
+
+fn parse_gpx(xml: &str) -> Vec<TrackPoint> {
+    let mut points: Vec<TrackPoint> = xml
+        .match_indices("<trkpt")
+        .filter_map(|(start, _)| {
+            let end = xml[start..].find("</trkpt>")? + start;
+            let segment = &xml[start..end];
+            let lat = extract_attr(segment, "lat")?.parse().ok()?;
+            let lon = extract_attr(segment, "lon")?.parse().ok()?;
+            let time = extract_tag(segment, "time")?;
+            let time = DateTime::parse_from_rfc3339(&time).ok()?.with_timezone(&Utc);
+            Some(TrackPoint { time, lat, lon })
+        })
+        .collect();
+
+    points.sort_by_key(|p| p.time);
+    points
+}
+
+fn extract_attr(xml: &str, key: &str) -> Option<String> {
+    let needle = format!("{key}=\"");
+    let start = xml.find(&needle)? + needle.len();
+    let end = xml[start..].find('"')?;
+    Some(xml[start..start + end].to_string())
+}
+
+fn extract_tag(xml: &str, key: &str) -> Option<String> {
+    let open = format!("<{key}>");
+    let close = format!("</{key}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)?;
+    Some(xml[start..start + end].trim().to_string())
+}
+
+fn interpolate(points: &[TrackPoint], photo_time: DateTime<Utc>) -> Option<(f64, f64)> {
+    let first = points.first()?;
+    let last = points.last()?;
+
+    if photo_time < first.time {
+        let slack = (first.time - photo_time).num_seconds();
+        return (slack <= TOLERANCE_SECONDS).then_some((first.lat, first.lon));
+    }
+
+    if photo_time > last.time {
+        let slack = (photo_time - last.time).num_seconds();
+        return (slack <= TOLERANCE_SECONDS).then_some((last.lat, last.lon));
+    }
+
+    let idx = points.partition_point(|p| p.time <= photo_time).min(points.len() - 1);
+    let p1 = &points[idx];
+    let p0 = &points[idx.saturating_sub(1)];
+
+    let total_ms = (p1.time - p0.time).num_milliseconds() as f64;
+    if total_ms <= 0.0 {
+        return Some((p0.lat, p0.lon));
+    }
+
+    let elapsed_ms = (photo_time - p0.time).num_milliseconds() as f64;
+    let f = elapsed_ms / total_ms;
+
+    Some((
+        p0.lat + (p1.lat - p0.lat) * f,
+        p0.lon + (p1.lon - p0.lon) * f,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(time: &str, lat: f64, lon: f64) -> TrackPoint {
+        TrackPoint {
+            time: DateTime::parse_from_rfc3339(time).unwrap().with_timezone(&Utc),
+            lat,
+            lon,
+        }
+    }
+
+    #[test]
+    fn test_parse_gpx_extracts_points_in_order() {
+        let xml = r#"
+            <gpx><trk><trkseg>
+                <trkpt lat="51.0" lon="-0.1"><time>2024-06-15T12:00:00Z</time></trkpt>
+                <trkpt lat="51.5" lon="-0.2"><time>2024-06-15T12:10:00Z</time></trkpt>
+            </trkseg></trk></gpx>
+        "#;
+        let points = parse_gpx(xml);
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].lat, 51.0);
+        assert_eq!(points[1].lat, 51.5);
+    }
+
+    #[test]
+    fn test_parse_gpx_sorts_out_of_order_points() {
+        let xml = r#"
+            <trkpt lat="51.5" lon="-0.2"><time>2024-06-15T12:10:00Z</time></trkpt>
+            <trkpt lat="51.0" lon="-0.1"><time>2024-06-15T12:00:00Z</time></trkpt>
+        "#;
+        let points = parse_gpx(xml);
+        assert_eq!(points[0].lat, 51.0);
+        assert_eq!(points[1].lat, 51.5);
+    }
+
+    #[test]
+    fn test_interpolate_midpoint() {
+        let points = vec![point("2024-06-15T12:00:00Z", 50.0, 0.0), point("2024-06-15T12:10:00Z", 51.0, 1.0)];
+        let photo_time = DateTime::parse_from_rfc3339("2024-06-15T12:05:00Z").unwrap().with_timezone(&Utc);
+
+        let (lat, lon) = interpolate(&points, photo_time).unwrap();
+        assert!((lat - 50.5).abs() < 1e-9);
+        assert!((lon - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_interpolate_empty_track_is_none() {
+        assert!(interpolate(&[], Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_interpolate_outside_range_within_tolerance_snaps_to_endpoint() {
+        let points = vec![point("2024-06-15T12:00:00Z", 50.0, 0.0), point("2024-06-15T12:10:00Z", 51.0, 1.0)];
+        let photo_time = DateTime::parse_from_rfc3339("2024-06-15T12:10:30Z").unwrap().with_timezone(&Utc);
+
+        assert_eq!(interpolate(&points, photo_time), Some((51.0, 1.0)));
+    }
+
+    #[test]
+    fn test_interpolate_outside_range_beyond_tolerance_is_none() {
+        let points = vec![point("2024-06-15T12:00:00Z", 50.0, 0.0), point("2024-06-15T12:10:00Z", 51.0, 1.0)];
+        let photo_time = DateTime::parse_from_rfc3339("2024-06-15T13:00:00Z").unwrap().with_timezone(&Utc);
+
+        assert!(interpolate(&points, photo_time).is_none());
+    }
+
+    #[test]
+    fn test_interpolate_duplicate_timestamps_avoids_divide_by_zero() {
+        let points = vec![point("2024-06-15T12:00:00Z", 50.0, 0.0), point("2024-06-15T12:00:00Z", 50.1, 0.1)];
+        let photo_time = DateTime::parse_from_rfc3339("2024-06-15T12:00:00Z").unwrap().with_timezone(&Utc);
+
+        assert!(interpolate(&points, photo_time).is_some());
+    }
+
+    #[test]
+    fn test_interpolate_single_point_within_tolerance() {
+        let points = vec![point("2024-06-15T12:00:00Z", 50.0, 0.0)];
+        let photo_time = DateTime::parse_from_rfc3339("2024-06-15T12:00:30Z").unwrap().with_timezone(&Utc);
+
+        assert_eq!(interpolate(&points, photo_time), Some((50.0, 0.0)));
+    }
+}
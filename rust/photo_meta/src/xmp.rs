@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use crate::models::Coord;
+
+/// Fields recovered from an XMP sidecar packet when a container carries no
+/// embedded EXIF of its own (common for edited RAWs and exported files).
+#[derive(Debug, Clone, Default)]
+pub struct XmpData {
+    pub timestamp: Option<String>,
+    pub lat: Option<f64>,
+    pub lon: Option<f64>,
+}
+
+impl XmpData {
+    fn is_empty(&self) -> bool {
+        self.timestamp.is_none() && self.lat.is_none() && self.lon.is_none()
+    }
+}
+
+/// Looks for a `.xmp` file with the same stem as `image_path` and parses the
+/// `exif:DateTimeOriginal`/`exif:GPSLatitude`/`exif:GPSLongitude` fields out
+/// of it. Returns `None` when no sidecar exists or it carries none of those
+/// fields.
+pub fn read_sidecar(image_path: &Path) -> Option<XmpData> {
+    let sidecar = image_path.with_extension("xmp");
+    let contents = std::fs::read_to_string(sidecar).ok()?;
+    let data = parse_xmp(&contents);
+    if data.is_empty() {
+        None
+    } else {
+        Some(data)
+    }
+}
+
+fn parse_xmp(xml: &str) -> XmpData {
+    let lat = extract_field(xml, "exif:GPSLatitude").and_then(|s| parse_gps_value(&s));
+    let lon = extract_field(xml, "exif:GPSLongitude").and_then(|s| parse_gps_value(&s));
+    // Validate through the same bounds check the rest of the crate uses, and
+    // drop either half if the pair doesn't resolve to a real coordinate.
+    let (lat, lon) = match lat.zip(lon).and_then(|(lat, lon)| Coord::new(lat, lon)) {
+        Some(coord) => (Some(coord.lat()), Some(coord.lon())),
+        None => (None, None),
+    };
+
+    XmpData {
+        timestamp: extract_field(xml, "exif:DateTimeOriginal"),
+        lat,
+        lon,
+    }
+}
+
+/// Parses an `exif:GPSLatitude`/`exif:GPSLongitude` value in either plain
+/// decimal degrees (`"40.7128"`) or Adobe's `deg,min.frac[NSEW]` form
+/// (`"40,42.768N"`), as written by Lightroom and similar tools.
+fn parse_gps_value(raw: &str) -> Option<f64> {
+    raw.parse().ok().or_else(|| parse_adobe_dms(raw))
+}
+
+fn parse_adobe_dms(raw: &str) -> Option<f64> {
+    let hemisphere = raw.chars().last()?;
+    let sign = match hemisphere {
+        'N' | 'E' => 1.0,
+        'S' | 'W' => -1.0,
+        _ => return None,
+    };
+
+    let (degrees, minutes) = raw[..raw.len() - hemisphere.len_utf8()].split_once(',')?;
+    let degrees: f64 = degrees.parse().ok()?;
+    let minutes: f64 = minutes.parse().ok()?;
+    Some(sign * (degrees + minutes / 60.0))
+}
+
+/// Extracts `key="value"` (XMP attribute form) or `<key>value</key>` (XMP
+/// element form), preferring the attribute form when both are present.
+fn extract_field(xml: &str, key: &str) -> Option<String> {
+    if let Some(value) = extract_attr(xml, key) {
+        return Some(value);
+    }
+    extract_tag(xml, key)
+}
+
+fn extract_attr(xml: &str, key: &str) -> Option<String> {
+    let needle = format!("{key}=\"");
+    let start = xml.find(&needle)? + needle.len();
+    let end = xml[start..].find('"')?;
+    Some(xml[start..start + end].to_string())
+}
+
+fn extract_tag(xml: &str, key: &str) -> Option<String> {
+    let open = format!("<{key}>");
+    let close = format!("</{key}>");
+    let start = xml.find(&open)? + open.len();
+    let end = xml[start..].find(&close)?;
+    Some(xml[start..start + end].trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_xmp_attribute_form() {
+        let xml = r#"<rdf:Description exif:DateTimeOriginal="2024-06-15T14:30:45" exif:GPSLatitude="40.7128" exif:GPSLongitude="-74.0060"/>"#;
+        let data = parse_xmp(xml);
+        assert_eq!(data.timestamp.as_deref(), Some("2024-06-15T14:30:45"));
+        assert_eq!(data.lat, Some(40.7128));
+        assert_eq!(data.lon, Some(-74.0060));
+    }
+
+    #[test]
+    fn test_parse_xmp_element_form() {
+        let xml = "<exif:DateTimeOriginal>2024-06-15T14:30:45</exif:DateTimeOriginal>";
+        let data = parse_xmp(xml);
+        assert_eq!(data.timestamp.as_deref(), Some("2024-06-15T14:30:45"));
+    }
+
+    #[test]
+    fn test_parse_xmp_missing_fields() {
+        let data = parse_xmp("<rdf:Description/>");
+        assert!(data.is_empty());
+    }
+
+    #[test]
+    fn test_parse_xmp_adobe_dms_form() {
+        let xml = r#"<rdf:Description exif:GPSLatitude="40,42.768N" exif:GPSLongitude="74,0.36W"/>"#;
+        let data = parse_xmp(xml);
+        assert!((data.lat.unwrap() - 40.7128).abs() < 1e-4);
+        assert!((data.lon.unwrap() - -74.006).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_parse_adobe_dms_south_and_east_are_signed() {
+        assert!((parse_adobe_dms("33,51.6S").unwrap() - -33.86).abs() < 1e-6);
+        assert!((parse_adobe_dms("151,12.96E").unwrap() - 151.216).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_parse_adobe_dms_rejects_invalid_hemisphere() {
+        assert!(parse_adobe_dms("40,42.768X").is_none());
+    }
+
+    #[test]
+    fn test_parse_xmp_rejects_out_of_range_coordinate() {
+        let xml = r#"<rdf:Description exif:GPSLatitude="140.0" exif:GPSLongitude="-74.0060"/>"#;
+        let data = parse_xmp(xml);
+        assert!(data.lat.is_none());
+        assert!(data.lon.is_none());
+    }
+}
@@ -1,6 +1,6 @@
 use photo_meta::extract_metadata;
 use pyo3::prelude::*;
-use pyo3::types::PyDict;
+use pyo3::types::{PyDateTime, PyDict};
 
 #[test]
 fn test_extract_metadata_integration_with_complete_exif() {
@@ -14,11 +14,11 @@ fn test_extract_metadata_integration_with_complete_exif() {
         assert!(dict.contains("lon").unwrap());
         assert!(dict.contains("location").unwrap());
 
-        // Verify date_taken is valid RFC3339
+        // Verify date_taken is a native, timezone-aware datetime
         let date_taken = dict.get_item("date_taken").unwrap();
         assert!(!date_taken.is_none());
-        let date_str: &str = date_taken.extract().unwrap();
-        assert!(date_str.parse::<chrono::DateTime<chrono::Utc>>().is_ok());
+        let py_dt: &PyDateTime = date_taken.downcast().unwrap();
+        assert!(py_dt.get_tzinfo().is_some());
 
         // Verify lat/lon are valid floats in correct range
         let lat: f64 = dict.get_item("lat").unwrap().extract().unwrap();